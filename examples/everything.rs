@@ -57,8 +57,8 @@ fn main() {
                 }
                 print!(" ");
             }
-            print!("\n");
+            println!();
         }
-        print!("\n");
+        println!();
     }
 }
@@ -0,0 +1,444 @@
+use std::{
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+    fs,
+    io::{
+        self,
+        Write
+    },
+    path::Path,
+    thread,
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+use crate::{
+    json,
+    strings::ColouredString,
+    terminal::{
+        Terminal,
+        take_utf8
+    }
+};
+
+
+/// The header of an asciicast v2 recording.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::AsciicastHeader;
+/// let header = AsciicastHeader {
+///     title : Some(String::from("Demo")),
+///     ..AsciicastHeader::new(80, 24)
+/// };
+/// assert_eq!(header.width, 80);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AsciicastHeader {
+    /// The number of columns of the terminal.
+    pub width           : usize,
+    /// The number of rows of the terminal.
+    pub height          : usize,
+    /// The unix time the recording started at.
+    pub timestamp       : Option<u64>,
+    /// The longest pause to keep when playing, in seconds.
+    pub idle_time_limit : Option<f64>,
+    /// The title of the recording.
+    pub title           : Option<String>,
+    /// Environment variables, such as `TERM` and `SHELL`.
+    pub env             : Vec<(String, String)>
+}
+
+/// Initialisation
+impl AsciicastHeader {
+
+    /// Create a new `AsciicastHeader` for a terminal of the given size.
+    pub fn new(width : usize, height : usize) -> AsciicastHeader {
+        return AsciicastHeader {
+            width,
+            height,
+            ..AsciicastHeader::default()
+        };
+    }
+
+}
+
+/// Serialisation
+impl AsciicastHeader {
+
+    /// Returns the header as a line of JSON.
+    ///
+    /// # Internal
+    fn serialize(&self) -> String {
+        let mut result = format!("{{\"version\": 2, \"width\": {}, \"height\": {}", self.width, self.height);
+        if let Some(timestamp) = self.timestamp {
+            result += &format!(", \"timestamp\": {}", timestamp);
+        }
+        if let Some(limit) = self.idle_time_limit {
+            result += &format!(", \"idle_time_limit\": {}", limit);
+        }
+        if let Some(title) = &self.title {
+            result += &format!(", \"title\": {}", json::quote(title));
+        }
+        if (! self.env.is_empty()) {
+            let env = self.env.iter().map(|(key, value)| format!("{}: {}", json::quote(key), json::quote(value))).collect::<Vec<String>>();
+            result += &format!(", \"env\": {{{}}}", env.join(", "));
+        }
+        result.push('}');
+        return result;
+    }
+
+}
+
+
+/// An event of an asciicast recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciicastEvent {
+    /// The time of the event, in seconds since the start.
+    pub time : f64,
+    /// The type of the event. `o` for output, `i` for
+    /// input, `m` for a marker and `r` for a resize.
+    pub kind : String,
+    /// The data of the event.
+    pub data : String
+}
+
+/// Serialisation
+impl AsciicastEvent {
+
+    /// Returns the event as a line of JSON.
+    ///
+    /// # Internal
+    fn serialize(&self) -> String {
+        let time = format!("{:.6}", self.time);
+        let time = time.trim_end_matches('0').trim_end_matches('.');
+        return format!("[{}, {}, {}]", time, json::quote(&self.kind), json::quote(&self.data));
+    }
+
+}
+
+
+/// An error produced when an asciicast recording can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsciicastParseError {
+    /// The line of the error, starting at 1.
+    pub line    : usize,
+    /// A description of the error.
+    pub message : String
+}
+
+impl Display for AsciicastParseError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}: {}", self.line, self.message);
+    }
+}
+
+impl Error for AsciicastParseError { }
+
+
+/// An asciicast v2 recording.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::Asciicast;
+/// let cast = Asciicast::parse(r#"{"version": 2, "width": 20, "height": 4}
+/// [0.5, "o", "Hello\r\n"]
+/// [1.0, "o", "\u001b[31mWorld\u001b[0m"]
+/// "#).unwrap();
+/// assert_eq!(cast.events.len(), 2);
+/// assert_eq!(cast.duration(), 1.0);
+///
+/// let screen = cast.final_screen();
+/// assert_eq!(screen.unformat(), "Hello\nWorld");
+/// assert_eq!(screen.style_at(6).fg(), Some(vibrance::Formatting::FgRed));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Asciicast {
+    /// The header of the recording.
+    pub header : AsciicastHeader,
+    /// The events of the recording, in order.
+    pub events : Vec<AsciicastEvent>
+}
+
+/// Parsing
+impl Asciicast {
+
+    /// Parse an asciicast v2 recording.
+    pub fn parse(text : &str) -> Result<Asciicast, AsciicastParseError> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| ! line.trim().is_empty());
+        let error     = |line : usize, message : &str| AsciicastParseError {
+            line    : line + 1,
+            message : String::from(message)
+        };
+        let (idx, line) = lines.next().ok_or_else(|| error(0, "Missing header."))?;
+        let header      = json::parse(line).map_err(|(_, message)| error(idx, &message))?;
+        if (header.get("version").and_then(|version| version.as_f64()) != Some(2.0)) {
+            return Err(error(idx, "Unsupported version."));
+        }
+        let size = |key : &str| header.get(key).and_then(|value| value.as_f64()).map(|value| value as usize).ok_or_else(|| error(idx, &format!("Missing `{}`.", key)));
+        let mut cast = Asciicast {
+            header : AsciicastHeader {
+                width           : size("width")?,
+                height          : size("height")?,
+                timestamp       : header.get("timestamp").and_then(|value| value.as_f64()).map(|value| value as u64),
+                idle_time_limit : header.get("idle_time_limit").and_then(|value| value.as_f64()),
+                title           : header.get("title").and_then(|value| value.as_str()).map(String::from),
+                env             : match (header.get("env")) {
+                    Some(json::Value::Object(entries)) => entries.iter()
+                        .filter_map(|(key, value)| value.as_str().map(|value| (key.clone(), value.to_string())))
+                        .collect(),
+                    _ => Vec::new()
+                }
+            },
+            events : Vec::new()
+        };
+        for (idx, line) in lines {
+            let event = json::parse(line).map_err(|(_, message)| error(idx, &message))?;
+            let json::Value::Array(values) = event else {
+                return Err(error(idx, "Expected an event."));
+            };
+            match (values.as_slice()) {
+                [json::Value::Number(time), json::Value::String(kind), json::Value::String(data)] => cast.events.push(AsciicastEvent {
+                    time : *time,
+                    kind : kind.clone(),
+                    data : data.clone()
+                }),
+                _ => return Err(error(idx, "Expected `[time, type, data]`."))
+            }
+        }
+        return Ok(cast);
+    }
+
+    /// Load an asciicast v2 recording from a file.
+    /// Parse errors are returned as `InvalidData`.
+    pub fn load<P : AsRef<Path>>(path : P) -> io::Result<Asciicast> {
+        let text = fs::read_to_string(path)?;
+        return Asciicast::parse(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error));
+    }
+
+    /// Returns the recording in the asciicast v2 format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{Asciicast, AsciicastEvent, AsciicastHeader};
+    /// let cast = Asciicast {
+    ///     header : AsciicastHeader::new(80, 24),
+    ///     events : vec![AsciicastEvent { time : 0.25, kind : String::from("o"), data : String::from("hi\n") }]
+    /// };
+    /// assert_eq!(cast.serialize(), "{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.25, \"o\", \"hi\\n\"]\n");
+    /// assert_eq!(Asciicast::parse(&cast.serialize()).unwrap(), cast);
+    /// ```
+    pub fn serialize(&self) -> String {
+        let mut result = self.header.serialize() + "\n";
+        for event in &self.events {
+            result += &event.serialize();
+            result.push('\n');
+        }
+        return result;
+    }
+
+}
+
+/// Playback
+impl Asciicast {
+
+    /// Returns the time of the last event, in seconds.
+    pub fn duration(&self) -> f64 {
+        return self.events.last().map_or(0.0, |event| event.time);
+    }
+
+    /// Returns every output event joined together.
+    pub fn output(&self) -> String {
+        return self.events.iter().filter(|event| event.kind == "o").map(|event| event.data.as_str()).collect();
+    }
+
+    /// Write the output of the recording to `out`, waiting
+    /// between events. The delays are divided by `speed`,
+    /// so `2.0` plays twice as fast and `f64::INFINITY`
+    /// writes everything without waiting. Pauses are capped
+    /// by the `idle_time_limit` of the header, and pauses too
+    /// long to wait for are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::Asciicast;
+    /// let cast = Asciicast::parse("{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.0, \"o\", \"a\"]\n[1e300, \"o\", \"b\"]\n").unwrap();
+    /// let mut out = Vec::new();
+    /// cast.play(&mut out, 1.0).unwrap();
+    /// assert_eq!(out, b"ab");
+    /// ```
+    pub fn play<W : Write>(&self, out : &mut W, speed : f64) -> io::Result<()> {
+        let mut last = 0.0;
+        for event in self.events.iter().filter(|event| event.kind == "o") {
+            let mut delay = (event.time - last).max(0.0);
+            last = event.time;
+            if let Some(limit) = self.header.idle_time_limit {
+                delay = delay.min(limit);
+            }
+            if let Ok(delay) = Duration::try_from_secs_f64(delay / speed) {
+                if (! delay.is_zero()) {
+                    out.flush()?;
+                    thread::sleep(delay);
+                }
+            }
+            out.write_all(event.data.as_bytes())?;
+        }
+        return out.flush();
+    }
+
+    /// Returns the contents of the terminal screen after every
+    /// event has been played, with one line per row and without
+    /// trailing blank rows and cells.
+    pub fn final_screen(&self) -> ColouredString {
+        let mut terminal = Terminal::new(self.header.width, self.header.height);
+        for event in &self.events {
+            match (event.kind.as_str()) {
+                "o" => terminal.feed(&event.data),
+                "r" => if let Some((width, height)) = event.data.split_once('x') {
+                    if let (Ok(width), Ok(height)) = (width.trim().parse(), height.trim().parse()) {
+                        terminal.resize(width, height);
+                    }
+                },
+                _   => { }
+            }
+        }
+        return terminal.contents();
+    }
+
+}
+
+
+/// Writes an asciicast v2 recording.
+///
+/// Events are timed by a clock which starts when the
+/// writer is created. [AsciicastWriter::advance] moves
+/// the clock forward without waiting, which allows
+/// recordings to be scripted.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use vibrance::{fg, Asciicast, AsciicastHeader, AsciicastWriter};
+/// let mut writer = AsciicastWriter::new_scripted(Vec::new(), &AsciicastHeader::new(20, 3)).unwrap();
+/// writer.frame(&fg::green("Loading")).unwrap();
+/// writer.advance(1.5);
+/// writer.frame(&fg::green("Done")).unwrap();
+/// write!(writer, "!").unwrap();
+///
+/// let cast = Asciicast::parse(&String::from_utf8(writer.into_inner()).unwrap()).unwrap();
+/// assert_eq!(cast.events.len(), 3);
+/// assert_eq!(cast.events[1].time, 1.5);
+/// assert_eq!(cast.final_screen().unformat(), "Done!");
+/// ```
+pub struct AsciicastWriter<W : Write> {
+    writer  : W,
+    /// `None` if the clock only moves with [AsciicastWriter::advance].
+    start   : Option<Instant>,
+    offset  : f64,
+    /// The end of a partial UTF-8 character written by `io::Write`.
+    pending : Vec<u8>
+}
+
+/// Initialisation
+impl<W : Write> AsciicastWriter<W> {
+
+    /// Create a new `AsciicastWriter` which times events
+    /// by the real time since it was created, and write
+    /// the header.
+    pub fn new(writer : W, header : &AsciicastHeader) -> io::Result<AsciicastWriter<W>> {
+        let mut writer = AsciicastWriter::new_scripted(writer, header)?;
+        writer.start = Some(Instant::now());
+        return Ok(writer);
+    }
+
+    /// Create a new `AsciicastWriter` whose clock only moves
+    /// with [AsciicastWriter::advance], and write the header.
+    pub fn new_scripted(mut writer : W, header : &AsciicastHeader) -> io::Result<AsciicastWriter<W>> {
+        writeln!(writer, "{}", header.serialize())?;
+        return Ok(AsciicastWriter {
+            writer,
+            start   : None,
+            offset  : 0.0,
+            pending : Vec::new()
+        });
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        return self.writer;
+    }
+
+}
+
+/// Recording
+impl<W : Write> AsciicastWriter<W> {
+
+    /// Returns the current time of the recording, in seconds.
+    pub fn time(&self) -> f64 {
+        return self.offset + self.start.map_or(0.0, |start| start.elapsed().as_secs_f64());
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&mut self, seconds : f64) {
+        self.offset += seconds.max(0.0);
+    }
+
+    /// Write an event at the current time.
+    pub fn event(&mut self, kind : &str, data : &str) -> io::Result<()> {
+        let event = AsciicastEvent {
+            time : self.time(),
+            kind : String::from(kind),
+            data : String::from(data)
+        };
+        return writeln!(self.writer, "{}", event.serialize());
+    }
+
+    /// Write raw output, such as escape sequences.
+    pub fn output(&mut self, text : &str) -> io::Result<()> {
+        return self.event("o", text);
+    }
+
+    /// Clear the screen and draw a frame at the top left.
+    /// Newlines are written as `\r\n`.
+    pub fn frame(&mut self, frame : &ColouredString) -> io::Result<()> {
+        let text = frame.format().replace('\n', "\r\n");
+        return self.output(&format!("\x1b[H\x1b[2J{}", text));
+    }
+
+    /// Add a marker, which players can jump to.
+    pub fn marker(&mut self, label : &str) -> io::Result<()> {
+        return self.event("m", label);
+    }
+
+    /// Record the terminal being resized.
+    pub fn resize(&mut self, width : usize, height : usize) -> io::Result<()> {
+        return self.event("r", &format!("{}x{}", width, height));
+    }
+
+}
+
+/// Writes the bytes as output events.
+impl<W : Write> Write for AsciicastWriter<W> {
+    fn write(&mut self, buf : &[u8]) -> io::Result<usize> {
+        let text = take_utf8(&mut self.pending, buf);
+        if (! text.is_empty()) {
+            self.output(&text)?;
+        }
+        return Ok(buf.len());
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        return self.writer.flush();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    consts::Formatting,
+    strings::{
+        ColouredString,
+        ColouredStringPart
+    }
+};
+
+
+/// Builds a `ColouredString` from a stack of styles.
+/// 
+/// Text added to the builder is formatted with every
+/// style currently on the stack.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{ColouredStringBuilder, Formatting};
+/// let mut b = ColouredStringBuilder::new();
+/// b.push_style(Formatting::Bold);
+/// b.text("error");
+/// b.pop_style();
+/// b.text(": ");
+/// b.with_style(Formatting::FgRed, |b| {
+///     b.text("failed ");
+///     b.with_style(Formatting::Underline, |b| {
+///         b.text("twice");
+///     });
+/// });
+/// let s = b.build();
+/// assert_eq!(s.unformat(), "error: failed twice");
+/// assert!(s.style_at(0).is_bold());
+/// assert_eq!(s.style_at(7).fg(), Some(Formatting::FgRed));
+/// assert!(s.style_at(14).is_underline());
+/// ```
+/// 
+/// # Aliases
+/// 
+/// Disable the `us` feature to use `ColouredStringBuilder` name.
+/// This is used by default.
+/// 
+/// Enable the `us` feature to use `ColoredStringBuilder` alias.
+#[derive(Debug, Clone)]
+pub struct ColouredStringBuilder {
+    stack : Vec<ColouredString>
+}
+
+/// Initialisation
+impl ColouredStringBuilder {
+    /// Create a new empty `ColouredStringBuilder` with no styles.
+    pub fn new() -> ColouredStringBuilder {
+        return ColouredStringBuilder {
+            stack : vec![ColouredString::new()]
+        };
+    }
+}
+
+impl Default for ColouredStringBuilder {
+    fn default() -> ColouredStringBuilder {
+        return ColouredStringBuilder::new();
+    }
+}
+
+/// Building
+impl ColouredStringBuilder {
+
+    /// Returns the `ColouredString` which text is currently added to.
+    /// 
+    /// # Internal
+    fn top(&mut self) -> &mut ColouredString {
+        return self.stack.last_mut().expect("Builder stack is never empty.");
+    }
+
+    /// Returns the number of styles currently on the stack.
+    pub fn depth(&self) -> usize {
+        return self.stack.len() - 1;
+    }
+
+    /// Push a style onto the stack.
+    /// Text added after this will be formatted with
+    /// it until it is popped.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `formatting` - The `Formatting` to push.
+    pub fn push_style(&mut self, formatting : Formatting) -> &mut ColouredStringBuilder {
+        let mut string = ColouredString::new();
+        string.formatting = vec![formatting];
+        self.stack.push(string);
+        return self;
+    }
+
+    /// Pop the most recently pushed style off the stack.
+    /// 
+    /// # Panics
+    /// 
+    /// * There are no styles on the stack.
+    pub fn pop_style(&mut self) -> &mut ColouredStringBuilder {
+        assert!(self.depth() > 0, "No style to pop.");
+        let string = self.stack.pop().expect("Builder stack is never empty.");
+        self.top().parts.push(ColouredStringPart::Sub(Box::new(string)));
+        return self;
+    }
+
+    /// Add text, formatted with every style on the stack.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Any object that can be converted into a `ColouredString`.
+    pub fn text<S : Into<ColouredString>>(&mut self, text : S) -> &mut ColouredStringBuilder {
+        let mut string = text.into();
+        if (string.formatting.is_empty() && string.semantic.is_none()) {
+            self.top().parts.append(&mut string.parts);
+        } else {
+            self.top().parts.push(ColouredStringPart::Sub(Box::new(string)));
+        }
+        return self;
+    }
+
+    /// Push a style, call the given function, then pop
+    /// the style along with any styles the function
+    /// left on the stack.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `formatting` - The `Formatting` to push.
+    /// * `function` - A function which adds the text to format.
+    pub fn with_style<F : FnOnce(&mut ColouredStringBuilder)>(&mut self, formatting : Formatting, function : F) -> &mut ColouredStringBuilder {
+        let depth = self.depth();
+        self.push_style(formatting);
+        function(self);
+        while (self.depth() > depth) {
+            self.pop_style();
+        }
+        return self;
+    }
+
+    /// Pop every remaining style and return the built `ColouredString`.
+    pub fn build(mut self) -> ColouredString {
+        while (self.depth() > 0) {
+            self.pop_style();
+        }
+        return self.stack.pop().expect("Builder stack is never empty.");
+    }
+
+}
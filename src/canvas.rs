@@ -0,0 +1,235 @@
+use crate::{
+    builder::ColouredStringBuilder,
+    strings::ColouredString,
+    terminal::{
+        CONTINUATION,
+        Cell
+    },
+    width::char_width
+};
+
+
+/// A grid of styled cells onto which `ColouredString`
+/// blocks can be drawn, then rendered as one `ColouredString`.
+///
+/// Blocks are drawn in layers. A cell is only replaced by a
+/// block on the same or a higher layer, so blocks can be drawn
+/// in any order. Parts of blocks outside of the canvas are
+/// clipped. Wide characters take two cells, and characters
+/// which take no columns are dropped.
+///
+/// Cells hold resolved styles, so semantic names are looked
+/// up in the active [Theme](crate::Theme) when a block is
+/// drawn, and the rendered text does not follow later
+/// changes to the theme.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{fg, Canvas, Formatting};
+/// let mut canvas = Canvas::new(12, 3);
+/// canvas.draw_layer(0, 2, 1, fg::red("top"));
+/// canvas.draw(0, 0, "background\nsecond line");
+/// canvas.draw(2, -3, "clipped");
+/// let s = canvas.render();
+/// assert_eq!(s.unformat(), "batopround\nsecond line\npped");
+/// assert_eq!(s.style_at(2).fg(), Some(Formatting::FgRed));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width  : usize,
+    height : usize,
+    cells  : Vec<Vec<Cell>>,
+    layers : Vec<Vec<i32>>
+}
+
+/// Initialisation
+impl Canvas {
+
+    /// Create a new blank `Canvas` of the given size.
+    pub fn new(width : usize, height : usize) -> Canvas {
+        return Canvas {
+            width,
+            height,
+            cells  : vec![vec![Cell::default(); width]; height],
+            layers : vec![vec![i32::MIN; width]; height]
+        };
+    }
+
+}
+
+/// Getters
+impl Canvas {
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /// Returns the cell at a row and column.
+    /// The right half of a wide character is `'\0'`.
+    ///
+    /// # Panics
+    /// Panics if the position is outside of the canvas.
+    pub fn cell(&self, row : usize, col : usize) -> Cell {
+        assert!(row < self.height && col < self.width, "Position out of bounds.");
+        return self.cells[row][col];
+    }
+
+}
+
+/// Drawing
+impl Canvas {
+
+    /// Draw a block on layer `0`, with its top left corner
+    /// at a row and column, which may be negative.
+    /// Each line of the block starts at the same column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::Canvas;
+    /// let mut canvas = Canvas::new(6, 1);
+    /// canvas.draw(0, 1, "日");
+    /// canvas.draw(0, 3, "x");
+    /// canvas.draw(0, 0, "本");
+    /// assert_eq!(canvas.render().unformat(), "本 x");
+    /// canvas.draw(0, 1, "語");
+    /// assert_eq!(canvas.render().unformat(), " 語x");
+    /// ```
+    pub fn draw<S : Into<ColouredString>>(&mut self, row : isize, col : isize, block : S) {
+        self.draw_layer(row, col, 0, block);
+    }
+
+    /// Draw a block on a layer. See [Canvas::draw].
+    pub fn draw_layer<S : Into<ColouredString>>(&mut self, row : isize, col : isize, layer : i32, block : S) {
+        let mut y = row;
+        let mut x = col;
+        for (ch, style) in block.into().styled_chars() {
+            if (ch == '\n') {
+                y += 1;
+                x  = col;
+                continue;
+            }
+            let width = char_width(ch) as isize;
+            if (width == 0) {
+                continue;
+            }
+            if (y >= 0 && x >= 0 && (y as usize) < self.height && ((x + width) as usize) <= self.width) {
+                let (y, x) = (y as usize, x as usize);
+                if ((x..x + width as usize).all(|x| self.layers[y][x] <= layer)) {
+                    self.put(y, x, Cell { ch, style }, layer);
+                    if (width == 2) {
+                        self.put(y, x + 1, Cell { ch : CONTINUATION, style }, layer);
+                    }
+                }
+            }
+            x += width;
+        }
+    }
+
+    /// Clear every cell.
+    pub fn clear(&mut self) {
+        *self = Canvas::new(self.width, self.height);
+    }
+
+    /// Set a cell, blanking the other half of any
+    /// wide character which it overwrites.
+    ///
+    /// # Internal
+    fn put(&mut self, row : usize, col : usize, cell : Cell, layer : i32) {
+        let old = self.cells[row][col];
+        if (old.ch == CONTINUATION && col > 0) {
+            self.cells[row][col - 1] = Cell::default();
+        }
+        if (char_width(old.ch) == 2 && col + 1 < self.width) {
+            self.cells[row][col + 1] = Cell::default();
+        }
+        self.cells[row][col]  = cell;
+        self.layers[row][col] = layer;
+    }
+
+}
+
+/// Rendering
+impl Canvas {
+
+    /// Returns a row as a `ColouredString`, without
+    /// trailing blank cells. Cells of the same style are
+    /// joined, so no more escapes are written than needed.
+    pub fn line(&self, row : usize) -> ColouredString {
+        let cells = &self.cells[row];
+        let end   = cells.iter().rposition(|cell| *cell != Cell::default()).map_or(0, |end| end + 1);
+        let mut result = ColouredStringBuilder::new();
+        for run in cells[..end].chunk_by(|a, b| a.style == b.style) {
+            let text = run.iter().map(|cell| cell.ch).filter(|ch| *ch != CONTINUATION).collect::<String>();
+            if (run[0].style.is_plain()) {
+                result.text(text);
+            } else {
+                result.text(ColouredString::from_formatting(text, run[0].style.formatting()));
+            }
+        }
+        return result.build();
+    }
+
+    /// Returns every row joined with newlines,
+    /// without trailing blank cells and rows.
+    pub fn render(&self) -> ColouredString {
+        let lines = (0..self.height).map(|row| self.line(row)).collect::<Vec<ColouredString>>();
+        let end   = lines.iter().rposition(|line| ! line.is_empty()).map_or(0, |end| end + 1);
+        let mut result = ColouredStringBuilder::new();
+        for (i, line) in lines.into_iter().take(end).enumerate() {
+            if (i > 0) {
+                result.text("\n");
+            }
+            result.text(line);
+        }
+        return result.build();
+    }
+
+}
+
+
+/// Arranges items into columns which fit in `width` terminal
+/// columns, like `ls`. Items run down each column in turn, and
+/// as few rows as possible are used. Columns are separated by
+/// `gap` spaces, and each line ends with a newline.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{columns, fg};
+/// let items = ["Cargo.toml", "README.md", "src", "target", "tests"].map(|item| fg::blue(item));
+/// assert_eq!(columns(items, 30, 2).unformat(), [
+///     "Cargo.toml  src     tests\n",
+///     "README.md   target\n"
+/// ].concat());
+/// ```
+pub fn columns<S : Into<ColouredString>, I : IntoIterator<Item = S>>(items : I, width : usize, gap : usize) -> ColouredString {
+    let items  = items.into_iter().map(Into::into).collect::<Vec<ColouredString>>();
+    let widths = items.iter().map(|item| item.width()).collect::<Vec<usize>>();
+    let rows   = (1..items.len()).find(|rows| {
+        let total = widths.chunks(*rows).map(|column| column.iter().max().copied().unwrap_or(0)).sum::<usize>();
+        return total + gap * (items.len().div_ceil(*rows) - 1) <= width;
+    }).unwrap_or(items.len());
+
+    let mut result = ColouredStringBuilder::new();
+    for row in 0..rows {
+        let mut fill = 0;
+        for (column, idx) in (row..items.len()).step_by(rows).enumerate() {
+            if (column > 0) {
+                result.text(" ".repeat(fill + gap));
+            }
+            result.text(items[idx].clone());
+            let column_width = widths[idx - row..(idx - row + rows).min(items.len())].iter().max().copied().unwrap_or(0);
+            fill = column_width - widths[idx];
+        }
+        result.text("\n");
+    }
+    return result.build();
+}
@@ -6,16 +6,16 @@ use std::fmt::{
 
 
 /// The format code prefix.
-const FORMAT_PREFIX : &'static str = "\x1b[";
+const FORMAT_PREFIX : &str = "\x1b[";
 /// The format code suffix.
-const FORMAT_SUFFIX : &'static str = "m";
+const FORMAT_SUFFIX : &str = "m";
 
 /// The reset format code.
-pub(crate) const FORMAT_RESET : &'static str = "\x1b[0m";
+pub(crate) const FORMAT_RESET : &str = "\x1b[0m";
 
 
 /// An enum of constants representing the different formatting codes.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Formatting {
 
     None,
@@ -86,6 +86,66 @@ pub enum Formatting {
 
 }
 
+/// The names of every formatting variant without arguments.
+/// These match the names of the formatting functions, with
+/// background colours prefixed by `bg_`.
+pub(crate) const FORMATTING_NAMES : [(&str, Formatting); 54] = [
+    ("reset"               , Formatting::None),
+    ("bold"                , Formatting::Bold),
+    ("faint"               , Formatting::Faint),
+    ("italic"              , Formatting::Italic),
+    ("underline"           , Formatting::Underline),
+    ("slow_blink"          , Formatting::SlowBlink),
+    ("fast_blink"          , Formatting::FastBlink),
+    ("invert"              , Formatting::Invert),
+    ("conceal"             , Formatting::Conceal),
+    ("strikethrough"       , Formatting::Strikethrough),
+    ("overline"            , Formatting::Overline),
+    ("reset_intensity"     , Formatting::ResetIntensity),
+    ("reset_italic"        , Formatting::ResetItalic),
+    ("reset_underline"     , Formatting::ResetUnderline),
+    ("reset_slow_blink"    , Formatting::ResetSlowBlink),
+    ("reset_fast_blink"    , Formatting::ResetFastBlink),
+    ("reset_invert"        , Formatting::ResetInvert),
+    ("reset_conceal"       , Formatting::ResetConceal),
+    ("reset_strikethrough" , Formatting::ResetStrikethrough),
+    ("reset_overline"      , Formatting::ResetOverline),
+    ("black"               , Formatting::FgBlack),
+    ("red"                 , Formatting::FgRed),
+    ("green"               , Formatting::FgGreen),
+    ("yellow"              , Formatting::FgYellow),
+    ("blue"                , Formatting::FgBlue),
+    ("magenta"             , Formatting::FgMagenta),
+    ("cyan"                , Formatting::FgCyan),
+    ("white"               , Formatting::FgWhite),
+    ("bright_black"        , Formatting::FgBrightBlack),
+    ("bright_red"          , Formatting::FgBrightRed),
+    ("bright_green"        , Formatting::FgBrightGreen),
+    ("bright_yellow"       , Formatting::FgBrightYellow),
+    ("bright_blue"         , Formatting::FgBrightBlue),
+    ("bright_magenta"      , Formatting::FgBrightMagenta),
+    ("bright_cyan"         , Formatting::FgBrightCyan),
+    ("bright_white"        , Formatting::FgBrightWhite),
+    ("fg_reset"            , Formatting::FgReset),
+    ("bg_black"            , Formatting::BgBlack),
+    ("bg_red"              , Formatting::BgRed),
+    ("bg_green"            , Formatting::BgGreen),
+    ("bg_yellow"           , Formatting::BgYellow),
+    ("bg_blue"             , Formatting::BgBlue),
+    ("bg_magenta"          , Formatting::BgMagenta),
+    ("bg_cyan"             , Formatting::BgCyan),
+    ("bg_white"            , Formatting::BgWhite),
+    ("bg_bright_black"     , Formatting::BgBrightBlack),
+    ("bg_bright_red"       , Formatting::BgBrightRed),
+    ("bg_bright_green"     , Formatting::BgBrightGreen),
+    ("bg_bright_yellow"    , Formatting::BgBrightYellow),
+    ("bg_bright_blue"      , Formatting::BgBrightBlue),
+    ("bg_bright_magenta"   , Formatting::BgBrightMagenta),
+    ("bg_bright_cyan"      , Formatting::BgBrightCyan),
+    ("bg_bright_white"     , Formatting::BgBrightWhite),
+    ("bg_reset"            , Formatting::BgReset)
+];
+
 /// Getters
 impl Formatting {
     /// Return the ANSI code of the formatting variant.
@@ -160,6 +220,175 @@ impl Formatting {
     }
 }
 
+/// Categories
+impl Formatting {
+    /// Returns the name of this formatting variant,
+    /// if it has one. See [FORMATTING_NAMES].
+    pub(crate) fn name(&self) -> Option<&'static str> {
+        return FORMATTING_NAMES.iter().find(|(_, formatting)| formatting == self).map(|(name, _)| *name);
+    }
+
+    /// Returns the formatting variant with the given name,
+    /// if there is one. See [FORMATTING_NAMES].
+    pub(crate) fn from_name(name : &str) -> Option<Formatting> {
+        return FORMATTING_NAMES.iter().find(|(other, _)| *other == name).map(|(_, formatting)| *formatting);
+    }
+
+    /// Returns `true` if this formatting variant sets the foreground colour.
+    pub(crate) fn is_fg(&self) -> bool {
+        return matches!(self,
+            Formatting::FgBlack         | Formatting::FgRed           | Formatting::FgGreen
+            | Formatting::FgYellow      | Formatting::FgBlue          | Formatting::FgMagenta
+            | Formatting::FgCyan        | Formatting::FgWhite         | Formatting::FgBrightBlack
+            | Formatting::FgBrightRed   | Formatting::FgBrightGreen   | Formatting::FgBrightYellow
+            | Formatting::FgBrightBlue  | Formatting::FgBrightMagenta | Formatting::FgBrightCyan
+            | Formatting::FgBrightWhite | Formatting::Fg8Bit(_)       | Formatting::Fg24Bit(_, _, _)
+            | Formatting::FgReset
+        );
+    }
+
+    /// Returns `true` if this formatting variant sets the background colour.
+    pub(crate) fn is_bg(&self) -> bool {
+        return matches!(self,
+            Formatting::BgBlack         | Formatting::BgRed           | Formatting::BgGreen
+            | Formatting::BgYellow      | Formatting::BgBlue          | Formatting::BgMagenta
+            | Formatting::BgCyan        | Formatting::BgWhite         | Formatting::BgBrightBlack
+            | Formatting::BgBrightRed   | Formatting::BgBrightGreen   | Formatting::BgBrightYellow
+            | Formatting::BgBrightBlue  | Formatting::BgBrightMagenta | Formatting::BgBrightCyan
+            | Formatting::BgBrightWhite | Formatting::Bg8Bit(_)       | Formatting::Bg24Bit(_, _, _)
+            | Formatting::BgReset
+        );
+    }
+
+    /// Returns `true` if this formatting variant removes
+    /// formatting rather than adding it.
+    pub fn is_reset(&self) -> bool {
+        return matches!(self,
+            Formatting::None
+            | Formatting::ResetIntensity
+            | Formatting::ResetItalic
+            | Formatting::ResetUnderline
+            | Formatting::ResetSlowBlink
+            | Formatting::ResetFastBlink
+            | Formatting::ResetInvert
+            | Formatting::ResetConceal
+            | Formatting::ResetStrikethrough
+            | Formatting::ResetOverline
+            | Formatting::FgReset
+            | Formatting::BgReset
+        );
+    }
+}
+
+/// Parsing
+impl Formatting {
+    /// Parse the parameters of an SGR escape sequence,
+    /// such as `01;38;5;196`, as used by `LS_COLORS`,
+    /// `GREP_COLORS` and similar variables.
+    /// 
+    /// Unsupported codes are skipped. An empty string
+    /// parses to no formatting.
+    /// Returns `None` if a parameter is not a number.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::Formatting;
+    /// assert_eq!(Formatting::parse_sgr("01;34"), Some(vec![Formatting::Bold, Formatting::FgBlue]));
+    /// assert_eq!(Formatting::parse_sgr("38;5;196;48;2;0;0;0"), Some(vec![Formatting::Fg8Bit(196), Formatting::Bg24Bit(0, 0, 0)]));
+    /// assert_eq!(Formatting::parse_sgr("0"), Some(vec![Formatting::None]));
+    /// assert_eq!(Formatting::parse_sgr("1;x"), None);
+    /// ```
+    pub fn parse_sgr(codes : &str) -> Option<Vec<Formatting>> {
+        if (codes.is_empty()) {
+            return Some(Vec::new());
+        }
+        let     codes  = codes.split(';').map(|code| if (code.is_empty()) {Some(0)} else {code.parse::<u16>().ok()}).collect::<Option<Vec<u16>>>()?;
+        let mut result = Vec::new();
+        let mut codes  = codes.into_iter();
+        while let Some(code) = codes.next() {
+            result.push(match (code) {
+
+                0   => Formatting::None,
+
+                1   => Formatting::Bold,
+                2   => Formatting::Faint,
+                3   => Formatting::Italic,
+                4   => Formatting::Underline,
+                5   => Formatting::SlowBlink,
+                6   => Formatting::FastBlink,
+                7   => Formatting::Invert,
+                8   => Formatting::Conceal,
+                9   => Formatting::Strikethrough,
+                53  => Formatting::Overline,
+
+                30  => Formatting::FgBlack,
+                31  => Formatting::FgRed,
+                32  => Formatting::FgGreen,
+                33  => Formatting::FgYellow,
+                34  => Formatting::FgBlue,
+                35  => Formatting::FgMagenta,
+                36  => Formatting::FgCyan,
+                37  => Formatting::FgWhite,
+                90  => Formatting::FgBrightBlack,
+                91  => Formatting::FgBrightRed,
+                92  => Formatting::FgBrightGreen,
+                93  => Formatting::FgBrightYellow,
+                94  => Formatting::FgBrightBlue,
+                95  => Formatting::FgBrightMagenta,
+                96  => Formatting::FgBrightCyan,
+                97  => Formatting::FgBrightWhite,
+
+                40  => Formatting::BgBlack,
+                41  => Formatting::BgRed,
+                42  => Formatting::BgGreen,
+                43  => Formatting::BgYellow,
+                44  => Formatting::BgBlue,
+                45  => Formatting::BgMagenta,
+                46  => Formatting::BgCyan,
+                47  => Formatting::BgWhite,
+                100 => Formatting::BgBrightBlack,
+                101 => Formatting::BgBrightRed,
+                102 => Formatting::BgBrightGreen,
+                103 => Formatting::BgBrightYellow,
+                104 => Formatting::BgBrightBlue,
+                105 => Formatting::BgBrightMagenta,
+                106 => Formatting::BgBrightCyan,
+                107 => Formatting::BgBrightWhite,
+
+                38 | 48 => {
+                    let mut channel = || codes.next().and_then(|n| u8::try_from(n).ok());
+                    match (channel()?) {
+                        5 if code == 38 => Formatting::Fg8Bit(channel()?),
+                        5               => Formatting::Bg8Bit(channel()?),
+                        2 if code == 38 => Formatting::Fg24Bit(channel()?, channel()?, channel()?),
+                        2               => Formatting::Bg24Bit(channel()?, channel()?, channel()?),
+                        _               => return None
+                    }
+                },
+
+                21 | 22 => Formatting::ResetIntensity,
+                23  => Formatting::ResetItalic,
+                24  => Formatting::ResetUnderline,
+                25  => Formatting::ResetSlowBlink,
+                26  => Formatting::ResetFastBlink,
+                27  => Formatting::ResetInvert,
+                28  => Formatting::ResetConceal,
+                29  => Formatting::ResetStrikethrough,
+                55  => Formatting::ResetOverline,
+
+                39  => Formatting::FgReset,
+
+                49  => Formatting::BgReset,
+
+                _   => continue
+
+            });
+        }
+        return Some(result);
+    }
+}
+
 /// Display
 impl Display for Formatting {
     fn fmt(&self, f : &mut Formatter<'_>) -> Result {
@@ -0,0 +1,113 @@
+use std::fmt::{
+    self,
+    Debug,
+    Display,
+    Formatter
+};
+
+use crate::{
+    strings::ColouredString,
+    syntax::languages
+};
+
+
+/// Formats a value with its `Debug` implementation and colours
+/// the output. Created by [debug_coloured].
+///
+/// The alternate flag is passed on, so `{:#}` writes the
+/// same as `{:#?}` would.
+pub struct DebugColoured<'l, T : Debug + ?Sized> {
+    value : &'l T
+}
+
+/// Getters
+impl<T : Debug + ?Sized> DebugColoured<'_, T> {
+
+    /// Returns the `Debug` output of the value as a `ColouredString`.
+    ///
+    /// * `alternate` - Whether to use the pretty `{:#?}` form.
+    pub fn coloured(&self, alternate : bool) -> ColouredString {
+        let text = if (alternate) {format!("{:#?}", self.value)} else {format!("{:?}", self.value)};
+        return languages::debug().highlight(&text);
+    }
+
+}
+
+impl<T : Debug + ?Sized> Display for DebugColoured<'_, T> {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.coloured(f.alternate()));
+    }
+}
+
+impl<T : Debug + ?Sized> Debug for DebugColoured<'_, T> {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return Display::fmt(self, f);
+    }
+}
+
+
+/// Colour the `Debug` output of a value.
+///
+/// The output is split into struct names, field names, strings,
+/// numbers, enum variants and punctuation, which are given the
+/// semantic names `syntax.type`, `syntax.property`, `syntax.string`,
+/// `syntax.number`, `syntax.constant` and `syntax.punctuation`,
+/// so their style comes from the active [Theme](crate::Theme).
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{debug_coloured, Formatting};
+/// #[derive(Debug)]
+/// struct Point { x : i32, label : Option<&'static str> }
+/// let point = Point { x : 3, label : Some("origin") };
+///
+/// let s = debug_coloured(&point).coloured(false);
+/// assert_eq!(s.unformat(), "Point { x: 3, label: Some(\"origin\") }");
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgYellow));
+/// assert_eq!(s.style_at(8).fg(), Some(Formatting::FgBlue));
+/// assert_eq!(s.style_at(11).fg(), Some(Formatting::FgCyan));
+/// assert_eq!(s.style_at(21).fg(), Some(Formatting::FgCyan));
+/// assert_eq!(s.style_at(26).fg(), Some(Formatting::FgGreen));
+///
+/// assert_eq!(format!("{:#}", debug_coloured(&point)), debug_coloured(&point).coloured(true).format());
+/// ```
+pub fn debug_coloured<T : Debug + ?Sized>(value : &T) -> DebugColoured<'_, T> {
+    return DebugColoured { value };
+}
+
+
+/// Prints and returns the value of an expression, like `dbg!`,
+/// with its `Debug` output coloured by [debug_coloured]. The
+/// file and line are written with the semantic name `muted`.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::cdbg;
+/// let a = cdbg!(2 * 3) + 1;
+/// assert_eq!(a, 7);
+/// let (b, c) = cdbg!(a, "text");
+/// assert_eq!((b, c), (7, "text"));
+/// ```
+#[macro_export]
+macro_rules! cdbg {
+    () => {
+        eprintln!("{}", $crate::macros::debug_location(file!(), line!(), column!()))
+    };
+    ($value:expr $(,)?) => {
+        match ($value) {
+            value => {
+                eprintln!("{} {} = {:#}",
+                    $crate::macros::debug_location(file!(), line!(), column!()),
+                    stringify!($value),
+                    $crate::macros::debug_coloured(&value)
+                );
+                value
+            }
+        }
+    };
+    ($($value:expr),+ $(,)?) => {
+        ($($crate::cdbg!($value)),+,)
+    };
+}
@@ -0,0 +1,318 @@
+use std::{
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+    str::FromStr
+};
+
+use crate::{
+    consts::Formatting,
+    styles::Style
+};
+
+
+/// An error produced when a style description can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleParseError {
+    /// The byte offset of the offending word in the description.
+    pub offset  : usize,
+    /// A description of the error.
+    pub message : String
+}
+
+impl Display for StyleParseError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.message);
+    }
+}
+
+impl Error for StyleParseError { }
+
+
+/// The attribute names accepted in style descriptions,
+/// along with the formatting they apply and the
+/// formatting which removes them.
+const ATTRIBUTES : [(&str, Formatting, Formatting); 24] = [
+    ("bold"          , Formatting::Bold          , Formatting::ResetIntensity     ),
+    ("faint"         , Formatting::Faint         , Formatting::ResetIntensity     ),
+    ("dim"           , Formatting::Faint         , Formatting::ResetIntensity     ),
+    ("italic"        , Formatting::Italic        , Formatting::ResetItalic        ),
+    ("underline"     , Formatting::Underline     , Formatting::ResetUnderline     ),
+    ("underlined"    , Formatting::Underline     , Formatting::ResetUnderline     ),
+    ("ul"            , Formatting::Underline     , Formatting::ResetUnderline     ),
+    ("slow_blink"    , Formatting::SlowBlink     , Formatting::ResetSlowBlink     ),
+    ("blink"         , Formatting::SlowBlink     , Formatting::ResetSlowBlink     ),
+    ("fast_blink"    , Formatting::FastBlink     , Formatting::ResetFastBlink     ),
+    ("rapid_blink"   , Formatting::FastBlink     , Formatting::ResetFastBlink     ),
+    ("invert"        , Formatting::Invert        , Formatting::ResetInvert        ),
+    ("inverse"       , Formatting::Invert        , Formatting::ResetInvert        ),
+    ("reverse"       , Formatting::Invert        , Formatting::ResetInvert        ),
+    ("conceal"       , Formatting::Conceal       , Formatting::ResetConceal       ),
+    ("concealed"     , Formatting::Conceal       , Formatting::ResetConceal       ),
+    ("hidden"        , Formatting::Conceal       , Formatting::ResetConceal       ),
+    ("strikethrough" , Formatting::Strikethrough , Formatting::ResetStrikethrough ),
+    ("strike"        , Formatting::Strikethrough , Formatting::ResetStrikethrough ),
+    ("crossed_out"   , Formatting::Strikethrough , Formatting::ResetStrikethrough ),
+    ("overline"      , Formatting::Overline      , Formatting::ResetOverline      ),
+    ("overlined"     , Formatting::Overline      , Formatting::ResetOverline      ),
+    ("reset"         , Formatting::None          , Formatting::None               ),
+    ("none"          , Formatting::None          , Formatting::None               )
+];
+
+
+/// Splits a description into words, keeping
+/// parenthesised groups such as `rgb(1, 2, 3)` together.
+/// Returns the byte offset of each word.
+fn words(text : &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start  = None;
+    let mut depth  = 0;
+    for (idx, ch) in text.char_indices() {
+        match (ch) {
+            '('                          => depth += 1,
+            ')'                          => depth -= 1,
+            _ if ch.is_whitespace() && depth <= 0 => {
+                if let Some(word_start) = start.take() {
+                    result.push((word_start, &text[word_start..idx]));
+                }
+                continue;
+            },
+            _ => { }
+        }
+        if (start.is_none()) {
+            start = Some(idx);
+        }
+    }
+    if let Some(word_start) = start {
+        result.push((word_start, &text[word_start..]));
+    }
+    return result;
+}
+
+/// Parses a colour.
+/// 
+/// Returns `Ok(None)` if the word does not look like a colour,
+/// and `Err` if it looks like one but is malformed.
+pub(crate) fn parse_colour(word : &str, background : bool) -> Result<Option<Formatting>, String> {
+    let rgb = |r, g, b| if (background) {Formatting::Bg24Bit(r, g, b)} else {Formatting::Fg24Bit(r, g, b)};
+
+    if let Some(hex) = word.strip_prefix('#') {
+        let digits = hex.chars().map(|ch| ch.to_digit(16).map(|n| n as u8)).collect::<Option<Vec<u8>>>();
+        return match (digits.as_deref()) {
+            Some(&[r, g, b])                => Ok(Some(rgb(r * 17, g * 17, b * 17))),
+            Some(&[r1, r2, g1, g2, b1, b2]) => Ok(Some(rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2))),
+            _ => Err(format!("Invalid hex colour `{}`. Expected 3 or 6 hex digits after `#`.", word))
+        };
+    }
+
+    if let Some(channels) = word.strip_prefix("rgb(") {
+        let channels = channels.strip_suffix(')').ok_or_else(|| format!("Invalid colour `{}`. Expected `)` after the channels.", word))?;
+        let channels = channels.split(',').map(|channel| {
+            let channel = channel.trim();
+            return channel.parse::<u8>().map_err(|_| format!("Invalid `rgb()` channel `{}`. Expected a number from 0 to 255.", channel));
+        }).collect::<Result<Vec<u8>, String>>()?;
+        return match (channels.as_slice()) {
+            &[r, g, b] => Ok(Some(rgb(r, g, b))),
+            _          => Err(format!("Invalid colour `{}`. Expected 3 channels in `rgb()`.", word))
+        };
+    }
+
+    if (word.starts_with(|ch : char| ch.is_ascii_digit())) {
+        let n = word.parse::<u8>().map_err(|_| format!("Invalid colour index `{}`. Expected a number from 0 to 255.", word))?;
+        return Ok(Some(if (background) {Formatting::Bg8Bit(n)} else {Formatting::Fg8Bit(n)}));
+    }
+
+    let name = match (word) {
+        "default"       => return Ok(Some(if (background) {Formatting::BgReset} else {Formatting::FgReset})),
+        "grey" | "gray" => "bright_black",
+        _ => match (word.strip_prefix("bright")) {
+            Some(rest) if ! rest.is_empty() && ! rest.starts_with('_') => return parse_colour(&format!("bright_{}", rest), background),
+            _ => word
+        }
+    };
+    let formatting = Formatting::from_name(&(if (background) {format!("bg_{}", name)} else {name.to_string()}));
+    return Ok(formatting.filter(|formatting| (formatting.is_fg() || formatting.is_bg()) && ! formatting.is_reset()));
+}
+
+/// Parses an attribute, such as `bold` or `nobold`.
+fn parse_attribute(word : &str) -> Option<Formatting> {
+    if let Some(formatting) = ATTRIBUTES.iter().find(|(name, _, _)| *name == word).map(|(_, formatting, _)| *formatting) {
+        return Some(formatting);
+    }
+    if let Some(name) = word.strip_prefix("no_").or_else(|| word.strip_prefix("no")) {
+        if let Some(formatting) = ATTRIBUTES.iter().find(|(other, _, _)| *other == name).map(|(_, _, reset)| *reset) {
+            return (formatting != Formatting::None).then_some(formatting);
+        }
+    }
+    return Formatting::from_name(word);
+}
+
+/// Parses a description into the formatting it applies, in order.
+fn parse_formatting(text : &str) -> Result<Vec<Formatting>, StyleParseError> {
+    let mut result  = Vec::new();
+    let mut colours = 0;
+    let mut words   = words(text).into_iter();
+    while let Some((offset, word)) = words.next() {
+        let error = |message : String| StyleParseError { offset, message };
+        let lower = word.to_lowercase().replace('-', "_");
+
+        if (lower == "on") {
+            let (offset, word) = words.next().ok_or_else(|| error(String::from("Expected a background colour after `on`.")))?;
+            let colour         = parse_colour(&word.to_lowercase().replace('-', "_"), true).map_err(|message| StyleParseError { offset, message })?;
+            result.push(colour.ok_or_else(|| StyleParseError { offset, message : format!("Unknown background colour `{}`.", word) })?);
+        }
+
+        else if let Some((prefix, colour)) = lower.split_once(':') {
+            let background = match (prefix) {
+                "fg" => false,
+                "bg" => true,
+                _    => return Err(error(format!("Unknown colour prefix `{}:`. Expected `fg:` or `bg:`.", prefix)))
+            };
+            result.push(parse_colour(colour, background).map_err(error)?.ok_or_else(|| error(format!("Unknown colour `{}`.", colour)))?);
+        }
+
+        else if (lower == "normal") {
+            colours += 1;
+        }
+
+        else if let Some(colour) = parse_colour(&lower, colours > 0).map_err(error)? {
+            if (colours > 1) {
+                return Err(error(format!("Unexpected colour `{}`. Only a foreground and a background colour can be given.", word)));
+            }
+            colours += 1;
+            result.push(colour);
+        }
+
+        else if let Some(attribute) = parse_attribute(&lower) {
+            result.push(attribute);
+        }
+
+        else {
+            return Err(error(format!("Unknown style `{}`.", word)));
+        }
+    }
+    return Ok(result);
+}
+
+
+/// Parses a style description.
+/// 
+/// A description is a list of words separated by whitespace.
+/// Words are case insensitive, and `-` can be used in place of `_`.
+/// 
+/// * Attributes: `bold`, `faint` / `dim`, `italic`, `underline` / `ul`,
+///   `slow_blink` / `blink`, `fast_blink`, `invert` / `reverse`,
+///   `conceal` / `hidden`, `strikethrough` / `strike` and `overline`.
+///   Prefix an attribute with `no` to remove it, such as `nobold`.
+///   `reset` removes everything before it.
+/// * Colours: a colour name such as `red` or `bright_red`, `default`,
+///   a 256 colour index such as `196`, a hex code such as `#ff8800`
+///   or `#f80`, or `rgb(255, 136, 0)`.
+/// * The first colour sets the foreground, and a second colour sets
+///   the background, as in git. `normal` skips a colour.
+/// * `on` followed by a colour sets the background.
+/// * `fg:` or `bg:` followed by a colour sets that colour.
+/// * Any formatting name, such as `reset_italic` or `bg_blue`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{Formatting, Style};
+/// let s : Style = "bold italic bright_red on rgb(30, 30, 30)".parse().unwrap();
+/// assert!(s.is_bold() && s.is_italic());
+/// assert_eq!(s.fg(), Some(Formatting::FgBrightRed));
+/// assert_eq!(s.bg(), Some(Formatting::Bg24Bit(30, 30, 30)));
+/// 
+/// let s : Style = "red reverse ul blue".parse().unwrap();
+/// assert!(s.is_invert() && s.is_underline());
+/// assert_eq!(s.bg(), Some(Formatting::BgBlue));
+/// 
+/// assert_eq!("fg:196".parse::<Style>().unwrap().fg(), Some(Formatting::Fg8Bit(196)));
+/// assert_eq!("#ff8800".parse::<Style>().unwrap().fg(), Some(Formatting::Fg24Bit(255, 136, 0)));
+/// 
+/// let error = "bold purple".parse::<Style>().unwrap_err();
+/// assert_eq!(error.offset, 5);
+/// assert_eq!(error.message, "Unknown style `purple`.");
+/// ```
+impl FromStr for Style {
+    type Err = StyleParseError;
+    fn from_str(text : &str) -> Result<Style, StyleParseError> {
+        return Ok(Style::from(parse_formatting(text)?));
+    }
+}
+
+/// Writes a description which parses back into the same `Style`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{Formatting, Style};
+/// let s = Style::from(vec![Formatting::Bold, Formatting::Fg8Bit(196), Formatting::BgBlue]);
+/// assert_eq!(s.to_string(), "bold fg:196 on blue");
+/// assert_eq!(s.to_string().parse::<Style>().unwrap(), s);
+/// ```
+impl Display for Style {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.formatting().iter().map(|formatting| formatting.description()).collect::<Vec<String>>().join(" "));
+    }
+}
+
+
+/// Parses a description of a single formatting variant.
+/// See the `FromStr` implementation of `Style` for the syntax.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::Formatting;
+/// assert_eq!("bold".parse::<Formatting>().unwrap(), Formatting::Bold);
+/// assert_eq!("on #202020".parse::<Formatting>().unwrap(), Formatting::Bg24Bit(32, 32, 32));
+/// assert_eq!("no-italic".parse::<Formatting>().unwrap(), Formatting::ResetItalic);
+/// assert!("bold red".parse::<Formatting>().is_err());
+/// ```
+impl FromStr for Formatting {
+    type Err = StyleParseError;
+    fn from_str(text : &str) -> Result<Formatting, StyleParseError> {
+        return match (parse_formatting(text)?.as_slice()) {
+            &[formatting] => Ok(formatting),
+            _             => Err(StyleParseError {
+                offset  : 0,
+                message : format!("Expected a single formatting, found `{}`.", text.trim())
+            })
+        };
+    }
+}
+
+/// Descriptions
+impl Formatting {
+    /// Returns a description of this formatting variant
+    /// which parses back into it.
+    /// 
+    /// `Display` writes the escape code of the variant instead.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::Formatting;
+    /// assert_eq!(Formatting::BgBrightRed.description(), "on bright_red");
+    /// assert_eq!(Formatting::Fg24Bit(255, 136, 0).description(), "#ff8800");
+    /// for formatting in [Formatting::None, Formatting::ResetOverline, Formatting::Bg8Bit(4), Formatting::FgReset] {
+    ///     assert_eq!(formatting.description().parse::<Formatting>().unwrap(), formatting);
+    /// }
+    /// ```
+    pub fn description(&self) -> String {
+        return match (self) {
+            Formatting::Fg8Bit(n)        => format!("fg:{}", n),
+            Formatting::Bg8Bit(n)        => format!("bg:{}", n),
+            Formatting::Fg24Bit(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            Formatting::Bg24Bit(r, g, b) => format!("on #{:02x}{:02x}{:02x}", r, g, b),
+            Formatting::FgReset          => String::from("fg:default"),
+            Formatting::BgReset          => String::from("bg:default"),
+            bg if bg.is_bg()             => format!("on {}", bg.name().and_then(|name| name.strip_prefix("bg_")).unwrap_or_default()),
+            other                        => other.name().unwrap_or_default().to_string()
+        };
+    }
+}
@@ -0,0 +1,493 @@
+use std::ops::Range;
+
+use crate::{
+    builder::ColouredStringBuilder,
+    strings::ColouredString,
+    width::char_width
+};
+
+
+/// The semantic name of the gutter, line numbers and arrows.
+const GUTTER    : &str = "diagnostic.gutter";
+/// The semantic name of secondary labels.
+const SECONDARY : &str = "diagnostic.secondary";
+
+/// Multi-line labels spanning more lines than this
+/// only show their first and last two lines.
+const MAX_SPAN_LINES : usize = 6;
+
+
+/// How serious a [Diagnostic] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    Note,
+    Help
+}
+
+impl Severity {
+
+    /// Returns the name of this severity, which is
+    /// also the semantic name used to style it.
+    pub fn name(&self) -> &'static str {
+        return match (self) {
+            Severity::Error   => "error",
+            Severity::Warning => "warning",
+            Severity::Info    => "info",
+            Severity::Note    => "note",
+            Severity::Help    => "help"
+        };
+    }
+
+}
+
+
+/// A labelled byte range of the source of a [Diagnostic].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    /// The byte range of the source.
+    pub range   : Range<usize>,
+    /// The message shown next to the range. May be empty.
+    pub message : String,
+    /// Whether this label is the cause of the diagnostic.
+    /// Primary labels are marked with `^` and secondary labels with `-`.
+    pub primary : bool
+}
+
+
+/// A message about some source text, rendered in the
+/// style of rustc.
+///
+/// Every part of the output has a semantic name, so it
+/// follows the active [Theme](crate::Theme). The severity
+/// and primary labels use the name of the [Severity], such
+/// as `error`, secondary labels use `diagnostic.secondary`,
+/// line numbers use `diagnostic.gutter` and the message
+/// uses `emphasis`.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::Diagnostic;
+/// let source = "let x: i32 = \"a\";\n";
+/// let diagnostic = Diagnostic::error("mismatched types")
+///     .with_code("E0308")
+///     .with_label(13..16, "expected `i32`, found `&str`")
+///     .with_secondary(7..10, "expected due to this")
+///     .with_help("try using a number");
+/// assert_eq!(diagnostic.render(source, "src/main.rs").unformat(), [
+///     "error[E0308]: mismatched types",
+///     " --> src/main.rs:1:14",
+///     "  |",
+///     "1 | let x: i32 = \"a\";",
+///     "  |        ---   ^^^ expected `i32`, found `&str`",
+///     "  |        |",
+///     "  |        expected due to this",
+///     "  |",
+///     "  = help: try using a number",
+///     ""
+/// ].join("\n"));
+/// ```
+///
+/// Labels can span multiple lines.
+///
+/// ```
+/// use vibrance::Diagnostic;
+/// let source = "fn main() {\n    loop {}\n}\n";
+/// let diagnostic = Diagnostic::warning("function never returns")
+///     .with_label(10..25, "this block loops forever");
+/// assert_eq!(diagnostic.render(source, "main.rs").unformat(), [
+///     "warning: function never returns",
+///     " --> main.rs:1:11",
+///     "  |",
+///     "1 |   fn main() {",
+///     "  |  ___________^",
+///     "2 | |     loop {}",
+///     "3 | | }",
+///     "  | |_^ this block loops forever",
+///     ""
+/// ].join("\n"));
+/// ```
+///
+/// Ranges which start or end inside a character
+/// are widened to the whole character.
+///
+/// ```
+/// use vibrance::Diagnostic;
+/// let diagnostic = Diagnostic::error("unknown name")
+///     .with_label(1..3, "here");
+/// assert_eq!(diagnostic.render("é = 1", "main.rs").unformat(), [
+///     "error: unknown name",
+///     " --> main.rs:1:1",
+///     "  |",
+///     "1 | é = 1",
+///     "  | ^^ here",
+///     ""
+/// ].join("\n"));
+/// ```
+///
+/// The location counts characters, while the markers
+/// line up with the columns the source takes.
+///
+/// ```
+/// use vibrance::Diagnostic;
+/// let diagnostic = Diagnostic::error("unknown name")
+///     .with_label(1..2, "here")
+///     .with_secondary(3..6, "wide");
+/// assert_eq!(diagnostic.render("\tx 日本", "main.rs").unformat(), [
+///     "error: unknown name",
+///     " --> main.rs:1:2",
+///     "  |",
+///     "1 |     x 日本",
+///     "  |     ^ -- wide",
+///     "  |     |",
+///     "  |     here",
+///     ""
+/// ].join("\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// How serious the diagnostic is.
+    pub severity : Severity,
+    /// An identifier such as `E0308`.
+    pub code     : Option<String>,
+    /// The main message.
+    pub message  : String,
+    /// The labelled ranges of the source.
+    pub labels   : Vec<Label>,
+    /// Notes shown after the source.
+    pub notes    : Vec<String>,
+    /// Help shown after the notes.
+    pub help     : Vec<String>
+}
+
+/// Initialisation
+impl Diagnostic {
+
+    /// Create a new `Diagnostic` with no labels.
+    pub fn new<S : Into<String>>(severity : Severity, message : S) -> Diagnostic {
+        return Diagnostic {
+            severity,
+            code     : None,
+            message  : message.into(),
+            labels   : Vec::new(),
+            notes    : Vec::new(),
+            help     : Vec::new()
+        };
+    }
+
+    /// Create a new error `Diagnostic`.
+    pub fn error<S : Into<String>>(message : S) -> Diagnostic {
+        return Diagnostic::new(Severity::Error, message);
+    }
+
+    /// Create a new warning `Diagnostic`.
+    pub fn warning<S : Into<String>>(message : S) -> Diagnostic {
+        return Diagnostic::new(Severity::Warning, message);
+    }
+
+    /// Returns this `Diagnostic` with a code.
+    pub fn with_code<S : Into<String>>(mut self, code : S) -> Diagnostic {
+        self.code = Some(code.into());
+        return self;
+    }
+
+    /// Returns this `Diagnostic` with a primary label.
+    pub fn with_label<S : Into<String>>(mut self, range : Range<usize>, message : S) -> Diagnostic {
+        self.labels.push(Label { range, message : message.into(), primary : true });
+        return self;
+    }
+
+    /// Returns this `Diagnostic` with a secondary label.
+    pub fn with_secondary<S : Into<String>>(mut self, range : Range<usize>, message : S) -> Diagnostic {
+        self.labels.push(Label { range, message : message.into(), primary : false });
+        return self;
+    }
+
+    /// Returns this `Diagnostic` with a note.
+    pub fn with_note<S : Into<String>>(mut self, note : S) -> Diagnostic {
+        self.notes.push(note.into());
+        return self;
+    }
+
+    /// Returns this `Diagnostic` with some help.
+    pub fn with_help<S : Into<String>>(mut self, help : S) -> Diagnostic {
+        self.help.push(help.into());
+        return self;
+    }
+
+}
+
+
+/// A row of output, as cells with an optional semantic name.
+#[derive(Default)]
+struct Row {
+    cells : Vec<(char, Option<&'static str>)>
+}
+
+impl Row {
+
+    /// Write a character, padding with spaces.
+    fn put(&mut self, column : usize, ch : char, name : Option<&'static str>) {
+        if (self.cells.len() <= column) {
+            self.cells.resize(column + 1, (' ', None));
+        }
+        self.cells[column] = (ch, name);
+    }
+
+    /// Write text, padding with spaces.
+    fn put_str(&mut self, column : usize, text : &str, name : Option<&'static str>) {
+        for (i, ch) in text.chars().enumerate() {
+            self.put(column + i, ch, name);
+        }
+    }
+
+    fn into_coloured(self) -> ColouredString {
+        let mut result = ColouredStringBuilder::new();
+        let     end    = self.cells.iter().rposition(|(ch, _)| *ch != ' ').map_or(0, |end| end + 1);
+        for run in self.cells[..end].chunk_by(|a, b| a.1 == b.1) {
+            let text = run.iter().map(|(ch, _)| *ch).collect::<String>();
+            match (run[0].1) {
+                Some(name) => result.text(ColouredString::from_semantic(text, name)),
+                None       => result.text(text)
+            };
+        }
+        return result.build();
+    }
+
+}
+
+
+/// A label resolved to lines and display columns.
+struct Span<'l> {
+    label        : &'l Label,
+    name         : &'static str,
+    start_line   : usize,
+    /// The character column of the start, shown in the location.
+    start_char   : usize,
+    start_column : usize,
+    end_line     : usize,
+    /// The column of the last character of the range.
+    end_column   : usize,
+    /// The gutter slot of a multi-line span.
+    slot         : usize
+}
+
+impl Span<'_> {
+
+    fn is_multiline(&self) -> bool {
+        return self.start_line != self.end_line;
+    }
+
+    fn marker(&self) -> char {
+        return if (self.label.primary) {'^'} else {'-'};
+    }
+
+}
+
+
+/// Returns a byte offset in a line, moved back
+/// to the start of the character it is in.
+fn char_start(line : &str, offset : usize) -> usize {
+    let mut offset = offset.min(line.len());
+    while (! line.is_char_boundary(offset)) {
+        offset -= 1;
+    }
+    return offset;
+}
+
+/// Returns the number of columns a character
+/// of the source takes.
+fn source_width(ch : char) -> usize {
+    return if (ch == '\t') {4} else {char_width(ch)};
+}
+
+/// Returns the display column of a byte offset in a line.
+fn column_of(line : &str, offset : usize) -> usize {
+    return line[..char_start(line, offset)].chars().map(source_width).sum();
+}
+
+/// Returns the last display column of the character
+/// at a byte offset in a line.
+fn end_column_of(line : &str, offset : usize) -> usize {
+    let width = line[char_start(line, offset)..].chars().next().map_or(0, source_width);
+    return column_of(line, offset) + width.saturating_sub(1);
+}
+
+
+/// Rendering
+impl Diagnostic {
+
+    /// Render this `Diagnostic` for some source text. Ranges are
+    /// clamped to the source, and tabs are shown as 4 spaces.
+    pub fn render(&self, source : &str, file : &str) -> ColouredString {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in source.split('\n') {
+            lines.push((start, line.strip_suffix('\r').unwrap_or(line)));
+            start += line.len() + 1;
+        }
+        let line_of = |offset : usize| lines.iter().rposition(|(start, _)| *start <= offset).unwrap_or(0);
+
+        let mut spans = Vec::new();
+        let mut slots = 0;
+        for label in &self.labels {
+            let start      = label.range.start.min(source.len());
+            let end        = label.range.end.clamp(start, source.len());
+            let start_line = line_of(start);
+            let last       = if (end > start) {end - 1} else {start};
+            let end_line   = line_of(last);
+            let multiline  = start_line != end_line;
+            spans.push(Span {
+                label,
+                name         : if (label.primary) {self.severity.name()} else {SECONDARY},
+                start_line,
+                start_char   : lines[start_line].1[..char_start(lines[start_line].1, start - lines[start_line].0)].chars().count(),
+                start_column : column_of(lines[start_line].1, start - lines[start_line].0),
+                end_line,
+                end_column   : end_column_of(lines[end_line].1, last - lines[end_line].0),
+                slot         : if (multiline) {slots += 1; slots - 1} else {0}
+            });
+        }
+
+        let mut shown = spans.iter().flat_map(|span| {
+            if (span.end_line - span.start_line < MAX_SPAN_LINES) {
+                (span.start_line..=span.end_line).collect::<Vec<usize>>()
+            } else {
+                vec![span.start_line, span.start_line + 1, span.end_line - 1, span.end_line]
+            }
+        }).collect::<Vec<usize>>();
+        shown.sort();
+        shown.dedup();
+        let width = shown.last().map_or(1, |line| (line + 1).to_string().len());
+        let area  = if (slots > 0) {slots + 1} else {0};
+        let text  = width + 3 + area;
+
+        let mut result = ColouredStringBuilder::new();
+        let mut header = Row::default();
+        header.put_str(0, self.severity.name(), Some(self.severity.name()));
+        if let Some(code) = &self.code {
+            header.put_str(header.cells.len(), &format!("[{}]", code), Some(self.severity.name()));
+        }
+        result.text(header.into_coloured() + ": " + ColouredString::from_semantic(self.message.as_str(), "emphasis") + "\n");
+
+        let mut rows = Vec::new();
+        if let Some(span) = spans.iter().find(|span| span.label.primary).or(spans.first()) {
+            let mut row = Row::default();
+            row.put_str(width, "-->", Some(GUTTER));
+            rows.push(row.into_coloured() + format!(" {}:{}:{}", file, span.start_line + 1, span.start_char + 1));
+        }
+        let blank = || {
+            let mut row = Row::default();
+            row.put(width + 1, '|', Some(GUTTER));
+            return row;
+        };
+        if (! spans.is_empty()) {
+            rows.push(blank().into_coloured());
+        }
+
+        // Draws the bars of the multi-line spans which are open on a row.
+        let bars = |row : &mut Row, line : usize, ended : &dyn Fn(&Span) -> bool| {
+            for span in spans.iter().filter(|span| span.is_multiline() && span.start_line < line && line <= span.end_line && ! ended(span)) {
+                row.put(width + 3 + span.slot, '|', Some(span.name));
+            }
+        };
+
+        for (i, &line) in shown.iter().enumerate() {
+            if (i > 0 && shown[i - 1] + 1 < line) {
+                let mut row = Row::default();
+                row.put_str(0, "...", Some(GUTTER));
+                bars(&mut row, line, &|_| false);
+                rows.push(row.into_coloured());
+            }
+
+            let mut row = Row::default();
+            row.put_str(0, &format!("{:>width$} |", line + 1, width = width), Some(GUTTER));
+            bars(&mut row, line, &|_| false);
+            row.put_str(text, &lines[line].1.replace('\t', "    "), None);
+            rows.push(row.into_coloured());
+
+            // Single line spans.
+            let mut single = spans.iter().filter(|span| ! span.is_multiline() && span.start_line == line).collect::<Vec<&Span>>();
+            single.sort_by_key(|span| std::cmp::Reverse(span.start_column));
+            if let Some(rightmost) = single.first() {
+                let mut row = blank();
+                bars(&mut row, line, &|span| span.start_line == line);
+                for span in single.iter().rev() {
+                    for column in span.start_column..=span.end_column.max(span.start_column) {
+                        row.put(text + column, span.marker(), Some(span.name));
+                    }
+                }
+                if (! rightmost.label.message.is_empty()) {
+                    let end = row.cells.len();
+                    row.put_str(end + 1, &rightmost.label.message, Some(rightmost.name));
+                }
+                rows.push(row.into_coloured());
+
+                let rest = single[1..].iter().filter(|span| ! span.label.message.is_empty()).collect::<Vec<&&Span>>();
+                if (! rest.is_empty()) {
+                    let mut row = blank();
+                    bars(&mut row, line, &|span| span.start_line == line);
+                    for span in &rest {
+                        row.put(text + span.start_column, '|', Some(span.name));
+                    }
+                    rows.push(row.into_coloured());
+                }
+                for (j, span) in rest.iter().enumerate() {
+                    let mut row = blank();
+                    bars(&mut row, line, &|span| span.start_line == line);
+                    for left in &rest[j + 1..] {
+                        row.put(text + left.start_column, '|', Some(left.name));
+                    }
+                    row.put_str(text + span.start_column, &span.label.message, Some(span.name));
+                    rows.push(row.into_coloured());
+                }
+            }
+
+            // Multi-line spans starting on this line.
+            for span in spans.iter().filter(|span| span.is_multiline() && span.start_line == line) {
+                let mut row = blank();
+                bars(&mut row, line, &|_| false);
+                for column in width + 4 + span.slot..text + span.start_column {
+                    row.put(column, '_', Some(span.name));
+                }
+                row.put(text + span.start_column, span.marker(), Some(span.name));
+                rows.push(row.into_coloured());
+            }
+
+            // Multi-line spans ending on this line.
+            for span in spans.iter().filter(|span| span.is_multiline() && span.end_line == line) {
+                let mut row = blank();
+                bars(&mut row, line, &|other| other.slot > span.slot && other.end_line == line);
+                for column in width + 4 + span.slot..text + span.end_column {
+                    row.put(column, '_', Some(span.name));
+                }
+                row.put(text + span.end_column, span.marker(), Some(span.name));
+                if (! span.label.message.is_empty()) {
+                    let end = row.cells.len();
+                    row.put_str(end + 1, &span.label.message, Some(span.name));
+                }
+                rows.push(row.into_coloured());
+            }
+        }
+
+        let extra = self.notes.iter().map(|note| ("note", note)).chain(self.help.iter().map(|help| ("help", help))).collect::<Vec<(&str, &String)>>();
+        if (! spans.is_empty() && ! extra.is_empty()) {
+            rows.push(blank().into_coloured());
+        }
+        for (name, message) in extra {
+            let mut row = Row::default();
+            row.put(width + 1, '=', Some(GUTTER));
+            let indent = " ".repeat(width + 4 + name.len() + 2);
+            rows.push(row.into_coloured() + " " + ColouredString::from_semantic(name, name) + ": " + message.replace('\n', &format!("\n{}", indent)));
+        }
+
+        for row in rows {
+            result.text(row + "\n");
+        }
+        return result.build();
+    }
+
+}
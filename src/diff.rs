@@ -0,0 +1,440 @@
+use crate::{
+    builder::ColouredStringBuilder,
+    consts::Formatting,
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// How a line or character changed between two texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffKind {
+    /// In both texts.
+    Equal,
+    /// Only in the old text.
+    Delete,
+    /// Only in the new text.
+    Insert
+}
+
+
+/// A line of a [Diff].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiffLine {
+    /// How the line changed.
+    pub kind : DiffKind,
+    /// The index of the line in the old text, unless it was inserted.
+    pub old  : Option<usize>,
+    /// The index of the line in the new text, unless it was deleted.
+    pub new  : Option<usize>
+}
+
+
+/// The styles used to render a [Diff].
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{DiffStyles, Formatting, Style};
+/// let styles = DiffStyles {
+///     delete : Style::from(Formatting::FgMagenta),
+///     ..DiffStyles::default()
+/// };
+/// assert!(styles.equal.is_plain());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiffStyles {
+    /// Lines in both texts.
+    pub equal            : Style,
+    /// Deleted lines.
+    pub delete           : Style,
+    /// Inserted lines.
+    pub insert           : Style,
+    /// Deleted characters inside a changed line,
+    /// applied on top of `delete`.
+    pub delete_highlight : Style,
+    /// Inserted characters inside a changed line,
+    /// applied on top of `insert`.
+    pub insert_highlight : Style,
+    /// Hunk headers such as `@@ -1,3 +1,4 @@`.
+    pub header           : Style,
+    /// The separator between the sides of a side by side view.
+    pub separator        : Style
+}
+
+impl Default for DiffStyles {
+    fn default() -> DiffStyles {
+        return DiffStyles {
+            equal            : Style::new(),
+            delete           : Style::from(Formatting::FgRed),
+            insert           : Style::from(Formatting::FgGreen),
+            delete_highlight : Style::from(Formatting::Invert),
+            insert_highlight : Style::from(Formatting::Invert),
+            header           : Style::from(Formatting::FgCyan),
+            separator        : Style::from(Formatting::Faint)
+        };
+    }
+}
+
+
+/// Returns the shortest edit script turning `old` into `new`,
+/// using the linear space algorithm of Eugene W. Myers.
+///
+/// # Internal
+pub(crate) fn myers<T : PartialEq>(old : &[T], new : &[T]) -> Vec<DiffKind> {
+    let mut result = Vec::with_capacity(old.len().max(new.len()));
+    myers_into(old, new, &mut result);
+    return result;
+}
+
+/// Appends the shortest edit script turning `old` into `new`,
+/// splitting both at the middle of an optimal path and
+/// recursing on each half.
+///
+/// # Internal
+fn myers_into<T : PartialEq>(old : &[T], new : &[T], result : &mut Vec<DiffKind>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let old    = &old[prefix..old.len() - suffix];
+    let new    = &new[prefix..new.len() - suffix];
+    result.extend(std::iter::repeat_n(DiffKind::Equal, prefix));
+    if (old.is_empty()) {
+        result.extend(std::iter::repeat_n(DiffKind::Insert, new.len()));
+    } else if (new.is_empty()) {
+        result.extend(std::iter::repeat_n(DiffKind::Delete, old.len()));
+    } else {
+        let (x, y, u, v) = middle_snake(old, new);
+        myers_into(&old[..x], &new[..y], result);
+        result.extend(std::iter::repeat_n(DiffKind::Equal, u - x));
+        myers_into(&old[u..], &new[v..], result);
+    }
+    result.extend(std::iter::repeat_n(DiffKind::Equal, suffix));
+}
+
+/// Returns the start and end of the diagonal run of
+/// matches in the middle of a shortest edit script, as
+/// `(x, y, u, v)`, by searching from both ends at once.
+///
+/// # Internal
+fn middle_snake<T : PartialEq>(old : &[T], new : &[T]) -> (usize, usize, usize, usize) {
+    let n      = old.len() as isize;
+    let m      = new.len() as isize;
+    let delta  = n - m;
+    let max    = (n + m + 1) / 2;
+    let offset = max + 1;
+    let index  = |k : isize| (k + offset) as usize;
+
+    // The furthest `x` reached on each diagonal `k = x - y`, from
+    // the start forwards and from the end backwards.
+    let mut forward  = vec![0isize; max as usize * 2 + 3];
+    let mut backward = vec![0isize; max as usize * 2 + 3];
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let start = if (k == -d || (k != d && forward[index(k - 1)] < forward[index(k + 1)])) {forward[index(k + 1)]} else {forward[index(k - 1)] + 1};
+            let mut x = start;
+            let mut y = x - k;
+            while (x < n && y < m && old[x as usize] == new[y as usize]) {
+                x += 1;
+                y += 1;
+            }
+            forward[index(k)] = x;
+            if (delta % 2 != 0 && (delta - k).abs() < d && x + backward[index(delta - k)] >= n) {
+                return (start as usize, (start - k) as usize, x as usize, y as usize);
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let start = if (k == -d || (k != d && backward[index(k - 1)] < backward[index(k + 1)])) {backward[index(k + 1)]} else {backward[index(k - 1)] + 1};
+            let mut x = start;
+            let mut y = x - k;
+            while (x < n && y < m && old[(n - x - 1) as usize] == new[(m - y - 1) as usize]) {
+                x += 1;
+                y += 1;
+            }
+            backward[index(k)] = x;
+            if (delta % 2 == 0 && (delta - k).abs() <= d && x + forward[index(delta - k)] >= n) {
+                return ((n - x) as usize, (m - y) as usize, (n - start) as usize, (m - start + k) as usize);
+            }
+        }
+    }
+    unreachable!("Every pair of sequences has an edit script.");
+}
+
+
+/// Returns the character level differences between two strings,
+/// with consecutive characters of the same kind grouped together.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{diff_chars, DiffKind};
+/// assert_eq!(diff_chars("colour", "color"), vec![
+///     (DiffKind::Equal,  String::from("colo")),
+///     (DiffKind::Delete, String::from("u")),
+///     (DiffKind::Equal,  String::from("r"))
+/// ]);
+/// ```
+pub fn diff_chars(old : &str, new : &str) -> Vec<(DiffKind, String)> {
+    let old_chars = old.chars().collect::<Vec<char>>();
+    let new_chars = new.chars().collect::<Vec<char>>();
+    let mut result = Vec::<(DiffKind, String)>::new();
+    let (mut i, mut j) = (0, 0);
+    for kind in myers(&old_chars, &new_chars) {
+        let ch = match (kind) {
+            DiffKind::Equal  => { i += 1; j += 1; old_chars[i - 1] },
+            DiffKind::Delete => { i += 1; old_chars[i - 1] },
+            DiffKind::Insert => { j += 1; new_chars[j - 1] }
+        };
+        match (result.last_mut()) {
+            Some((last, text)) if *last == kind => text.push(ch),
+            _                                   => result.push((kind, ch.to_string()))
+        }
+    }
+    return result;
+}
+
+
+/// The line level differences between two texts.
+///
+/// Each line keeps its own formatting when rendered. Changed
+/// lines are paired up inside each run of deletions and
+/// insertions, and the characters which differ between the
+/// pairs are highlighted.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{Diff, DiffKind, DiffStyles};
+/// let diff = Diff::new("a\nb\nc\nd", "a\nB\nc\nd\ne");
+/// assert!(! diff.is_equal());
+/// assert_eq!(diff.changes().iter().map(|line| line.kind).collect::<Vec<DiffKind>>(), vec![
+///     DiffKind::Equal, DiffKind::Delete, DiffKind::Insert, DiffKind::Equal, DiffKind::Equal, DiffKind::Insert
+/// ]);
+/// assert_eq!(diff.unified(1, &DiffStyles::default()).unformat(), "@@ -1,4 +1,5 @@\n a\n-b\n+B\n c\n d\n+e\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Diff {
+    old     : Vec<ColouredString>,
+    new     : Vec<ColouredString>,
+    changes : Vec<DiffLine>
+}
+
+/// Initialisation
+impl Diff {
+
+    /// Compare two texts line by line. Formatting is ignored
+    /// when comparing, so only the text of lines can differ.
+    pub fn new<A : Into<ColouredString>, B : Into<ColouredString>>(old : A, new : B) -> Diff {
+        let old       = old.into().lines();
+        let new       = new.into().lines();
+        let old_text  = old.iter().map(|line| line.unformat()).collect::<Vec<String>>();
+        let new_text  = new.iter().map(|line| line.unformat()).collect::<Vec<String>>();
+        let mut changes = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        for kind in myers(&old_text, &new_text) {
+            changes.push(match (kind) {
+                DiffKind::Equal  => { i += 1; j += 1; DiffLine { kind, old : Some(i - 1), new : Some(j - 1) } },
+                DiffKind::Delete => { i += 1; DiffLine { kind, old : Some(i - 1), new : None } },
+                DiffKind::Insert => { j += 1; DiffLine { kind, old : None, new : Some(j - 1) } }
+            });
+        }
+        return Diff { old, new, changes };
+    }
+
+}
+
+/// Getters
+impl Diff {
+
+    /// Returns every line of both texts, in order.
+    pub fn changes(&self) -> &[DiffLine] {
+        return &self.changes;
+    }
+
+    /// Returns `true` if the texts have the same lines.
+    pub fn is_equal(&self) -> bool {
+        return self.changes.iter().all(|line| line.kind == DiffKind::Equal);
+    }
+
+}
+
+/// Rendering
+impl Diff {
+
+    /// Returns the text of every line of the diff in its style,
+    /// with the paired up changed lines highlighted.
+    ///
+    /// # Internal
+    fn styled(&self, styles : &DiffStyles) -> Vec<ColouredString> {
+        let mut result = Vec::new();
+        let mut start  = 0;
+        while (start < self.changes.len()) {
+            if (self.changes[start].kind == DiffKind::Equal) {
+                result.push(styles.equal.paint(self.old[self.changes[start].old.unwrap()].clone()));
+                start += 1;
+                continue;
+            }
+            let end     = self.changes[start..].iter().position(|line| line.kind == DiffKind::Equal).map_or(self.changes.len(), |end| start + end);
+            let deleted = self.changes[start..end].iter().filter_map(|line| line.old).collect::<Vec<usize>>();
+            let added   = self.changes[start..end].iter().filter_map(|line| line.new).collect::<Vec<usize>>();
+            let mut old = deleted.iter().map(|i| styles.delete.paint(self.old[*i].clone())).collect::<Vec<ColouredString>>();
+            let mut new = added.iter().map(|j| styles.insert.paint(self.new[*j].clone())).collect::<Vec<ColouredString>>();
+            for (old, new) in old.iter_mut().zip(new.iter_mut()) {
+                let (mut i, mut j) = (0, 0);
+                for (kind, text) in diff_chars(&old.unformat(), &new.unformat()) {
+                    match (kind) {
+                        DiffKind::Equal  => {
+                            i += text.len();
+                            j += text.len();
+                        },
+                        DiffKind::Delete => {
+                            old.format_range(i..i + text.len(), styles.delete_highlight.formatting());
+                            i += text.len();
+                        },
+                        DiffKind::Insert => {
+                            new.format_range(j..j + text.len(), styles.insert_highlight.formatting());
+                            j += text.len();
+                        }
+                    }
+                }
+            }
+            let (mut old, mut new) = (old.into_iter(), new.into_iter());
+            for line in &self.changes[start..end] {
+                result.push(match (line.kind) {
+                    DiffKind::Delete => old.next().unwrap(),
+                    _                => new.next().unwrap()
+                });
+            }
+            start = end;
+        }
+        return result;
+    }
+
+    /// Render every line of both texts, prefixed by
+    /// ` `, `-` or `+` and a space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{Diff, DiffStyles, RenderMode};
+    /// let diff   = Diff::new("one\ntwo", "one\nthree");
+    /// let inline = diff.inline(&DiffStyles::default());
+    /// assert_eq!(inline.unformat(), "  one\n- two\n+ three\n");
+    /// assert!(inline.render(RenderMode::Tags).contains("<fg=green>t<invert>hree</invert></fg>"));
+    /// ```
+    pub fn inline(&self, styles : &DiffStyles) -> ColouredString {
+        let mut result = ColouredStringBuilder::new();
+        for (line, text) in self.changes.iter().zip(self.styled(styles)) {
+            result.text(marker(line.kind, styles) + " " + text + "\n");
+        }
+        return result.build();
+    }
+
+    /// Render the changes in the unified format, with the given
+    /// number of equal lines around each change. Changes which
+    /// are close together are joined into a single hunk.
+    pub fn unified(&self, context : usize, styles : &DiffStyles) -> ColouredString {
+        let styled = self.styled(styles);
+        let mut result = ColouredStringBuilder::new();
+        let mut start  = 0;
+        while let Some(first) = self.changes[start..].iter().position(|line| line.kind != DiffKind::Equal).map(|i| start + i) {
+            let hunk_start = first.saturating_sub(context).max(start);
+            let mut end    = first;
+            while let Some(next) = self.changes[end + 1..].iter().position(|line| line.kind != DiffKind::Equal).map(|i| end + 1 + i) {
+                if (next - end - 1 > context * 2) {
+                    break;
+                }
+                end = next;
+            }
+            let hunk_end = (end + 1 + context).min(self.changes.len());
+            let hunk     = &self.changes[hunk_start..hunk_end];
+
+            let old_start = self.changes[..hunk_start].iter().filter(|line| line.old.is_some()).count();
+            let new_start = self.changes[..hunk_start].iter().filter(|line| line.new.is_some()).count();
+            let old_count = hunk.iter().filter(|line| line.old.is_some()).count();
+            let new_count = hunk.iter().filter(|line| line.new.is_some()).count();
+            let range     = |start : usize, count : usize| if (count == 0) {format!("{},0", start)} else {format!("{},{}", start + 1, count)};
+            result.text(styles.header.paint(format!("@@ -{} +{} @@", range(old_start, old_count), range(new_start, new_count))) + "\n");
+            for (line, text) in hunk.iter().zip(&styled[hunk_start..hunk_end]) {
+                result.text(marker(line.kind, styles) + text.clone() + "\n");
+            }
+            start = hunk_end;
+        }
+        return result.build();
+    }
+
+    /// Render the old text on the left and the new text on the
+    /// right, in a view `width` columns wide. Changed lines are
+    /// placed side by side, and long lines are cut off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{Diff, DiffStyles};
+    /// let diff = Diff::new("same\nold\ngone", "same\nnew");
+    /// assert_eq!(diff.side_by_side(23, &DiffStyles::default()).unformat(), [
+    ///     "  same     │   same",
+    ///     "- old      │ + new",
+    ///     "- gone     │",
+    ///     ""
+    /// ].join("\n"));
+    ///
+    /// let diff = Diff::new("日本語の文", "日本");
+    /// assert_eq!(diff.side_by_side(17, &DiffStyles::default()).unformat(), [
+    ///     "- 日本  │ + 日本",
+    ///     ""
+    /// ].join("\n"));
+    /// ```
+    pub fn side_by_side(&self, width : usize, styles : &DiffStyles) -> ColouredString {
+        let column = width.saturating_sub(3) / 2;
+        let styled = self.styled(styles);
+        let cell   = |kind : Option<DiffKind>, text : Option<&ColouredString>| {
+            let Some(kind) = kind else {
+                return ColouredString::new();
+            };
+            let mut cell = marker(kind, styles) + " " + text.cloned().unwrap_or_default();
+            cell.truncate_width(column);
+            return cell;
+        };
+
+        let mut rows  = Vec::<(Option<usize>, Option<usize>)>::new();
+        let mut start = 0;
+        while (start < self.changes.len()) {
+            if (self.changes[start].kind == DiffKind::Equal) {
+                rows.push((Some(start), Some(start)));
+                start += 1;
+                continue;
+            }
+            let end     = self.changes[start..].iter().position(|line| line.kind == DiffKind::Equal).map_or(self.changes.len(), |end| start + end);
+            let deleted = (start..end).filter(|i| self.changes[*i].kind == DiffKind::Delete).collect::<Vec<usize>>();
+            let added   = (start..end).filter(|i| self.changes[*i].kind == DiffKind::Insert).collect::<Vec<usize>>();
+            for row in 0..deleted.len().max(added.len()) {
+                rows.push((deleted.get(row).copied(), added.get(row).copied()));
+            }
+            start = end;
+        }
+
+        let mut result = ColouredStringBuilder::new();
+        for (left, right) in rows {
+            let left   = cell(left.map(|i| self.changes[i].kind), left.map(|i| &styled[i]));
+            let right  = cell(right.map(|i| self.changes[i].kind), right.map(|i| &styled[i]));
+            let length = left.width();
+            result.text(left + " ".repeat(column - length + 1) + styles.separator.paint("│"));
+            if (! right.is_empty()) {
+                result.text(" " + right);
+            }
+            result.text("\n");
+        }
+        return result.build();
+    }
+
+}
+
+
+/// Returns the marker of a line.
+fn marker(kind : DiffKind, styles : &DiffStyles) -> ColouredString {
+    return match (kind) {
+        DiffKind::Equal  => styles.equal.paint(" "),
+        DiffKind::Delete => styles.delete.paint("-"),
+        DiffKind::Insert => styles.insert.paint("+")
+    };
+}
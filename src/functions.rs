@@ -20,6 +20,18 @@ macro_rules! function {
 }
 
 
+/// Generates a function which takes in a `Colourisable`
+/// and returns a string tagged with a semantic name.
+macro_rules! semantic {
+    ($name:ident) => {
+        /// A semantic tagging function.
+        pub fn $name<S : Colourisable>(from : S) -> ColouredString {
+            ColouredString::from_semantic(from, stringify!($name))
+        }
+    };
+}
+
+
 /// Text styling functions.
 pub mod style {
     use super::*;
@@ -93,3 +105,17 @@ pub mod bg {
     function!(cbit_24(r:u8,g:u8,b:u8) , Formatting::Bg24Bit(r,g,b)  );
     function!(reset                   , Formatting::BgReset         );
 }
+/// Semantic tagging functions.
+/// The style of each tag is looked up in the active
+/// `Theme` when the string is formatted.
+pub mod semantic {
+    use super::*;
+    semantic!(error    );
+    semantic!(warning  );
+    semantic!(info     );
+    semantic!(note     );
+    semantic!(help     );
+    semantic!(success  );
+    semantic!(emphasis );
+    semantic!(muted    );
+}
@@ -0,0 +1,209 @@
+use std::ops::Range;
+
+use crate::{
+    consts::Formatting,
+    strings::ColouredString
+};
+
+
+/// A function which returns byte ranges of the given text.
+pub type RangeFunction = Box<dyn Fn(&str) -> Vec<Range<usize>>>;
+
+
+/// Something which finds byte ranges of text to highlight.
+pub enum Matcher {
+    /// Matches every occurrence of a substring.
+    Literal(String),
+    /// Matches every run of characters which satisfy a predicate.
+    Class(Box<dyn Fn(char) -> bool>),
+    /// Matches every byte range returned by a function.
+    Ranges(RangeFunction)
+}
+
+/// Matching
+impl Matcher {
+    /// Returns the byte ranges of every match in the given text.
+    /// 
+    /// # Internal
+    fn find(&self, text : &str) -> Vec<Range<usize>> {
+        return match (self) {
+
+            Matcher::Literal(pattern) => if (pattern.is_empty()) {
+                Vec::new()
+            } else {
+                text.match_indices(pattern.as_str()).map(|(idx, matched)| idx .. idx + matched.len()).collect()
+            },
+
+            Matcher::Class(predicate) => {
+                let mut result = Vec::new();
+                let mut start  = None;
+                for (idx, ch) in text.char_indices() {
+                    match (start, predicate(ch)) {
+                        (Some(run_start), false) => {
+                            result.push(run_start .. idx);
+                            start = None;
+                        },
+                        (None, true) => start = Some(idx),
+                        _            => { }
+                    }
+                }
+                if let Some(run_start) = start {
+                    result.push(run_start .. text.len());
+                }
+                result
+            },
+
+            Matcher::Ranges(function) => function(text).into_iter().filter(|range| range.start < range.end).collect()
+
+        };
+    }
+}
+
+
+/// A rule used by a `Highlighter`.
+struct HighlightRule {
+    matcher    : Matcher,
+    formatting : Vec<Formatting>,
+    priority   : i32
+}
+
+
+/// Applies formatting to every match of a set of rules.
+/// 
+/// When matches overlap, the match of the rule with the
+/// highest priority is kept. Rules with equal priority
+/// are resolved in the order they were added.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{Formatting, Highlighter};
+/// let h = Highlighter::new()
+///     .literal("error", vec![Formatting::FgRed])
+///     .class(|ch| ch.is_ascii_digit(), vec![Formatting::FgBlue])
+///     .ranges(|text| text.find(':').map(|idx| vec![0..idx]).unwrap_or_default(), vec![Formatting::Bold]);
+/// let s = h.highlight("main.rs: error at line 12");
+/// assert_eq!(s.unformat(), "main.rs: error at line 12");
+/// assert!(s.style_at(0).is_bold());
+/// assert_eq!(s.style_at(9).fg(), Some(Formatting::FgRed));
+/// assert_eq!(s.style_at(23).fg(), Some(Formatting::FgBlue));
+/// ```
+pub struct Highlighter {
+    rules : Vec<HighlightRule>
+}
+
+/// Initialisation
+impl Highlighter {
+    /// Create a new `Highlighter` with no rules.
+    pub fn new() -> Highlighter {
+        return Highlighter {
+            rules : Vec::new()
+        };
+    }
+}
+
+impl Default for Highlighter {
+    fn default() -> Highlighter {
+        return Highlighter::new();
+    }
+}
+
+/// Rules
+impl Highlighter {
+
+    /// Add a rule to this `Highlighter`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `matcher` - The `Matcher` which finds the text to format.
+    /// * `formatting` - A `Vec<Formatting>` to apply to each match.
+    /// * `priority` - Matches of higher priority rules win over overlapping matches of lower priority rules.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Highlighter, Matcher};
+    /// let h = Highlighter::new()
+    ///     .rule(Matcher::Literal(String::from("foo")), vec![Formatting::FgRed], 0)
+    ///     .rule(Matcher::Literal(String::from("oba")), vec![Formatting::FgBlue], 1);
+    /// let s = h.highlight("foobar");
+    /// assert_eq!(s.style_at(0).fg(), None);
+    /// assert_eq!(s.style_at(2).fg(), Some(Formatting::FgBlue));
+    /// ```
+    pub fn rule(mut self, matcher : Matcher, formatting : Vec<Formatting>, priority : i32) -> Highlighter {
+        self.rules.push(HighlightRule {
+            matcher,
+            formatting,
+            priority
+        });
+        return self;
+    }
+
+    /// Add a rule matching every occurrence of a substring, with a priority of `0`.
+    pub fn literal<S : Into<String>>(self, pattern : S, formatting : Vec<Formatting>) -> Highlighter {
+        return self.rule(Matcher::Literal(pattern.into()), formatting, 0);
+    }
+
+    /// Add a rule matching every run of characters which
+    /// satisfy a predicate, with a priority of `0`.
+    pub fn class<F : Fn(char) -> bool + 'static>(self, predicate : F, formatting : Vec<Formatting>) -> Highlighter {
+        return self.rule(Matcher::Class(Box::new(predicate)), formatting, 0);
+    }
+
+    /// Add a rule matching every byte range returned by
+    /// a function, with a priority of `0`.
+    /// The ranges must lie on character boundaries.
+    pub fn ranges<F : Fn(&str) -> Vec<Range<usize>> + 'static>(self, function : F, formatting : Vec<Formatting>) -> Highlighter {
+        return self.rule(Matcher::Ranges(Box::new(function)), formatting, 0);
+    }
+
+}
+
+/// Highlighting
+impl Highlighter {
+
+    /// Returns the non-overlapping matches which will be
+    /// formatted, sorted by position.
+    /// 
+    /// # Internal
+    fn matches(&self, text : &str) -> Vec<(Range<usize>, &Vec<Formatting>)> {
+        let mut candidates = Vec::new();
+        for (order, rule) in self.rules.iter().enumerate() {
+            for range in rule.matcher.find(text) {
+                candidates.push((rule.priority, order, range, &rule.formatting));
+            }
+        }
+        candidates.sort_by(|(a_priority, a_order, a_range, _), (b_priority, b_order, b_range, _)|
+            b_priority.cmp(a_priority)
+                .then(a_order.cmp(b_order))
+                .then(a_range.start.cmp(&b_range.start))
+        );
+        let mut accepted : Vec<(Range<usize>, &Vec<Formatting>)> = Vec::new();
+        for (_, _, range, formatting) in candidates {
+            if (accepted.iter().all(|(other, _)| range.end <= other.start || other.end <= range.start)) {
+                accepted.push((range, formatting));
+            }
+        }
+        accepted.sort_by_key(|(range, _)| range.start);
+        return accepted;
+    }
+
+    /// Apply the rules of this `Highlighter` to some text.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Any object that can be converted into a `ColouredString`.
+    /// 
+    /// # Panics
+    /// 
+    /// * A range returned by a `Matcher::Ranges` function is out of bounds.
+    pub fn highlight<S : Into<ColouredString>>(&self, text : S) -> ColouredString {
+        let mut result = text.into();
+        let     plain  = result.unformat();
+        for (range, formatting) in self.matches(&plain) {
+            result.format_range(range, formatting.clone());
+        }
+        return result;
+    }
+
+}
@@ -0,0 +1,414 @@
+use std::{
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter,
+        Write
+    }
+};
+
+
+/// The most arrays and objects which may be nested in a document.
+const MAX_DEPTH : usize = 128;
+
+
+/// A tree of structured data, as read from JSON.
+///
+/// See [Value::pretty] to write it with colour.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::Value;
+/// let value = Value::parse(r#"{"name": "vibrance", "tags": ["colour", 1]}"#).unwrap();
+/// assert_eq!(value.get("name").and_then(Value::as_str), Some("vibrance"));
+/// assert_eq!(value.get("tags"), Some(&Value::Array(vec![Value::from("colour"), Value::from(1.0)])));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Keys are kept in the order they were written.
+    Object(Vec<(String, Value)>)
+}
+
+/// Initialisation
+impl Value {
+
+    /// Parse a JSON document.
+    /// Arrays and objects may be nested at most 128 deep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::Value;
+    /// assert_eq!(Value::parse("[true, null]").unwrap(), Value::Array(vec![Value::Bool(true), Value::Null]));
+    /// assert_eq!(Value::parse("[true,]").unwrap_err().offset, 6);
+    /// assert_eq!(Value::parse("\u{a0}1").unwrap_err().offset, 0);
+    /// assert!(Value::parse(&"[".repeat(100_000)).is_err());
+    ///
+    /// assert_eq!(Value::parse(r#""\ud83d\ude00""#).unwrap(), Value::from("😀"));
+    /// for invalid in ["01", "-", "1.", "1e", r#""\u+123""#, "\"\t\"", r#""\ud83d""#, r#""\ude00""#, r#""\ud83d\u0041""#] {
+    ///     assert!(Value::parse(invalid).is_err(), "{}", invalid);
+    /// }
+    /// ```
+    pub fn parse(text : &str) -> Result<Value, JsonParseError> {
+        return parse(text).map_err(|(offset, message)| JsonParseError { offset, message });
+    }
+
+}
+
+/// Getters
+impl Value {
+
+    /// Returns the value of a key, if this is an object containing it.
+    pub fn get(&self, key : &str) -> Option<&Value> {
+        return match (self) {
+            Value::Object(entries) => entries.iter().find(|(other, _)| other == key).map(|(_, value)| value),
+            _                      => None
+        };
+    }
+
+    /// Returns the boolean, if this is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        return match (self) {
+            Value::Bool(value) => Some(*value),
+            _                  => None
+        };
+    }
+
+    /// Returns the string, if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        return match (self) {
+            Value::String(string) => Some(string),
+            _                     => None
+        };
+    }
+
+    /// Returns the number, if this is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        return match (self) {
+            Value::Number(number) => Some(*number),
+            _                     => None
+        };
+    }
+
+    /// Returns the values, if this is an array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        return match (self) {
+            Value::Array(values) => Some(values),
+            _                    => None
+        };
+    }
+
+    /// Returns the number of values in this value,
+    /// including itself.
+    ///
+    /// # Internal
+    pub(crate) fn count(&self) -> usize {
+        return 1 + match (self) {
+            Value::Array(values)   => values.iter().map(Value::count).sum(),
+            Value::Object(entries) => entries.iter().map(|(_, value)| value.count()).sum(),
+            _                      => 0
+        };
+    }
+
+}
+
+impl From<bool> for Value {
+    fn from(value : bool) -> Value {
+        return Value::Bool(value);
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value : f64) -> Value {
+        return Value::Number(value);
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value : &str) -> Value {
+        return Value::String(value.to_string());
+    }
+}
+
+impl From<String> for Value {
+    fn from(value : String) -> Value {
+        return Value::String(value);
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(values : Vec<Value>) -> Value {
+        return Value::Array(values);
+    }
+}
+
+
+/// An error produced when JSON can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonParseError {
+    /// The byte offset of the error.
+    pub offset  : usize,
+    /// A description of the error.
+    pub message : String
+}
+
+impl Display for JsonParseError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}: {}", self.offset, self.message);
+    }
+}
+
+impl Error for JsonParseError { }
+
+
+/// Parses a JSON document.
+/// On failure, returns the byte offset of the error and a description.
+///
+/// # Internal
+pub(crate) fn parse(text : &str) -> Result<Value, (usize, String)> {
+    return parse_with_offsets(text).map(|(value, _)| value);
+}
+
+/// Parses a JSON document, also returning the byte offset
+/// of every value in the order they start, so the value
+/// at index `0` is the document itself. See [Value::count].
+///
+/// # Internal
+pub(crate) fn parse_with_offsets(text : &str) -> Result<(Value, Vec<usize>), (usize, String)> {
+    let mut parser = Parser { text, offset : 0, depth : 0, offsets : Vec::new() };
+    let     value  = parser.value()?;
+    parser.whitespace();
+    if (parser.offset < text.len()) {
+        return Err(parser.error("Unexpected text after the value."));
+    }
+    return Ok((value, parser.offsets));
+}
+
+/// Writes a string as a quoted and escaped JSON string.
+///
+/// # Internal
+pub(crate) fn quote(string : &str) -> String {
+    let mut result = String::from("\"");
+    for ch in string.chars() {
+        match (ch) {
+            '"'              => result += "\\\"",
+            '\\'             => result += "\\\\",
+            '\n'             => result += "\\n",
+            '\r'             => result += "\\r",
+            '\t'             => result += "\\t",
+            ch if ch < ' ' || ch == '\x7f' => { let _ = write!(result, "\\u{:04x}", ch as u32); },
+            ch               => result.push(ch)
+        }
+    }
+    result.push('"');
+    return result;
+}
+
+
+/// The state of a JSON parser.
+struct Parser<'l> {
+    text    : &'l str,
+    offset  : usize,
+    /// The number of arrays and objects currently open.
+    depth   : usize,
+    /// The offset of every value started so far.
+    offsets : Vec<usize>
+}
+
+impl Parser<'_> {
+
+    fn error(&self, message : &str) -> (usize, String) {
+        return (self.offset, String::from(message));
+    }
+
+    fn peek(&self) -> Option<char> {
+        return self.text[self.offset..].chars().next();
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(ch) = self.peek() {
+            if (! matches!(ch, ' ' | '\t' | '\n' | '\r')) {
+                break;
+            }
+            self.offset += ch.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected : &str) -> Result<(), (usize, String)> {
+        if (! self.text[self.offset..].starts_with(expected)) {
+            return Err(self.error(&format!("Expected `{}`.", expected)));
+        }
+        self.offset += expected.len();
+        return Ok(());
+    }
+
+    fn value(&mut self) -> Result<Value, (usize, String)> {
+        self.whitespace();
+        self.offsets.push(self.offset);
+        return match (self.peek()) {
+            Some('n') => self.expect("null").map(|_| Value::Null),
+            Some('t') => self.expect("true").map(|_| Value::Bool(true)),
+            Some('f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some('"') => self.string().map(Value::String),
+            Some('[') => {
+                self.open()?;
+                let mut values = Vec::new();
+                self.whitespace();
+                if (self.peek() == Some(']')) {
+                    self.close();
+                    return Ok(Value::Array(values));
+                }
+                loop {
+                    values.push(self.value()?);
+                    self.whitespace();
+                    match (self.peek()) {
+                        Some(',') => self.offset += 1,
+                        Some(']') => { self.close(); break; },
+                        _         => return Err(self.error("Expected `,` or `]`."))
+                    }
+                }
+                Ok(Value::Array(values))
+            },
+            Some('{') => {
+                self.open()?;
+                let mut entries = Vec::new();
+                self.whitespace();
+                if (self.peek() == Some('}')) {
+                    self.close();
+                    return Ok(Value::Object(entries));
+                }
+                loop {
+                    self.whitespace();
+                    if (self.peek() != Some('"')) {
+                        return Err(self.error("Expected a string key."));
+                    }
+                    let key = self.string()?;
+                    self.whitespace();
+                    self.expect(":")?;
+                    entries.push((key, self.value()?));
+                    self.whitespace();
+                    match (self.peek()) {
+                        Some(',') => self.offset += 1,
+                        Some('}') => { self.close(); break; },
+                        _         => return Err(self.error("Expected `,` or `}`."))
+                    }
+                }
+                Ok(Value::Object(entries))
+            },
+            Some(ch) if ch == '-' || ch.is_ascii_digit() => self.number(),
+            Some(_) => Err(self.error("Expected a value.")),
+            None    => Err(self.error("Unexpected end of text."))
+        };
+    }
+
+    /// Step over the bracket which opens an array or object.
+    fn open(&mut self) -> Result<(), (usize, String)> {
+        if (self.depth >= MAX_DEPTH) {
+            return Err(self.error("Too deeply nested."));
+        }
+        self.depth  += 1;
+        self.offset += 1;
+        return Ok(());
+    }
+
+    /// Step over the bracket which closes an array or object.
+    fn close(&mut self) {
+        self.depth  -= 1;
+        self.offset += 1;
+    }
+
+    /// Step over a run of digits, returning how many there were.
+    fn digits(&mut self) -> usize {
+        let count = self.text[self.offset..].bytes().take_while(u8::is_ascii_digit).count();
+        self.offset += count;
+        return count;
+    }
+
+    fn number(&mut self) -> Result<Value, (usize, String)> {
+        let start   = self.offset;
+        let invalid = |parser : &Parser| (start, format!("Invalid number `{}`.", &parser.text[start..parser.offset]));
+        if (self.peek() == Some('-')) {
+            self.offset += 1;
+        }
+        let integer = self.offset;
+        let count   = self.digits();
+        if (count == 0 || (count > 1 && self.text[integer..].starts_with('0'))) {
+            return Err(invalid(self));
+        }
+        if (self.peek() == Some('.')) {
+            self.offset += 1;
+            if (self.digits() == 0) {
+                return Err(invalid(self));
+            }
+        }
+        if (matches!(self.peek(), Some('e' | 'E'))) {
+            self.offset += 1;
+            if (matches!(self.peek(), Some('+' | '-'))) {
+                self.offset += 1;
+            }
+            if (self.digits() == 0) {
+                return Err(invalid(self));
+            }
+        }
+        return self.text[start..self.offset].parse::<f64>()
+            .map(Value::Number)
+            .map_err(|_| invalid(self));
+    }
+
+    fn string(&mut self) -> Result<String, (usize, String)> {
+        self.expect("\"")?;
+        let mut result = String::new();
+        loop {
+            let ch = self.peek().ok_or_else(|| self.error("Unterminated string."))?;
+            self.offset += ch.len_utf8();
+            match (ch) {
+                '"'  => return Ok(result),
+                '\\' => {
+                    let escape = self.peek().ok_or_else(|| self.error("Unterminated string."))?;
+                    self.offset += escape.len_utf8();
+                    result.push(match (escape) {
+                        '"'  => '"',
+                        '\\' => '\\',
+                        '/'  => '/',
+                        'b'  => '\x08',
+                        'f'  => '\x0c',
+                        'n'  => '\n',
+                        'r'  => '\r',
+                        't'  => '\t',
+                        'u'  => {
+                            let start    = self.offset - 2;
+                            let mut code = self.hex4()?;
+                            if ((0xd800..0xdc00).contains(&code)) {
+                                let low = self.expect("\\u").and_then(|_| self.hex4()).ok().filter(|low| (0xdc00..0xe000).contains(low));
+                                let Some(low) = low else {
+                                    return Err((start, String::from("Unpaired surrogate.")));
+                                };
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            char::from_u32(code).ok_or_else(|| (start, String::from("Unpaired surrogate.")))?
+                        },
+                        _ => return Err(self.error("Invalid escape sequence."))
+                    });
+                },
+                ch if ch < ' ' => return Err((self.offset - 1, String::from("Unescaped control character in string."))),
+                ch => result.push(ch)
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, (usize, String)> {
+        let digits = self.text.get(self.offset..self.offset + 4).filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        let digits = digits.ok_or_else(|| self.error("Expected 4 hex digits."))?;
+        let code   = u32::from_str_radix(digits, 16).map_err(|_| self.error("Expected 4 hex digits."))?;
+        self.offset += 4;
+        return Ok(code);
+    }
+
+}
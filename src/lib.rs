@@ -13,6 +13,7 @@
 
 
 #![allow(unused_parens)]
+#![allow(clippy::needless_return)]
 
 
 mod traits;
@@ -24,15 +25,161 @@ pub use traits::Colourisable as Colorizable;
 mod consts;
 pub use consts::Formatting;
 
+mod styles;
+pub use styles::Style;
+
+mod descriptions;
+pub use descriptions::StyleParseError;
+
+mod theme;
+pub use theme::{
+    Theme,
+    ThemeParseError
+};
+
 mod strings;
 #[cfg(not(feature = "us"))]
 pub use strings::ColouredString;
 #[cfg(feature = "us")]
 pub use strings::ColouredString as ColoredString;
 
+mod builder;
+#[cfg(not(feature = "us"))]
+pub use builder::ColouredStringBuilder;
+#[cfg(feature = "us")]
+pub use builder::ColouredStringBuilder as ColoredStringBuilder;
+
+#[doc(hidden)]
+pub mod macros;
+
+mod highlight;
+pub use highlight::{
+    Highlighter,
+    Matcher
+};
+
+mod ls_colors;
+pub use ls_colors::{
+    FileKind,
+    LsColors
+};
+
+mod json;
+pub use json::{
+    JsonParseError,
+    Value
+};
+
+mod palette;
+pub use palette::{
+    Palette,
+    PaletteParseError,
+    Rgb
+};
+
+mod svg;
+pub use svg::SvgOptions;
+
+mod terminal;
+pub use terminal::{
+    Cell,
+    Terminal
+};
+
+mod asciicast;
+pub use asciicast::{
+    Asciicast,
+    AsciicastEvent,
+    AsciicastHeader,
+    AsciicastParseError,
+    AsciicastWriter
+};
+
+mod diff;
+pub use diff::{
+    Diff,
+    DiffKind,
+    DiffLine,
+    DiffStyles,
+    diff_chars
+};
+
+mod diagnostic;
+pub use diagnostic::{
+    Diagnostic,
+    Label,
+    Severity
+};
+
+mod syntax;
+pub use syntax::{
+    Language,
+    TokenFunction,
+    TokenPattern,
+    TokenRule
+};
+
+mod pretty;
+pub use pretty::PrettyOptions;
+
+mod width;
+pub use width::{
+    char_width,
+    str_width
+};
+
+mod panel;
+pub use panel::{
+    Alignment,
+    Border,
+    Panel
+};
+
+mod canvas;
+pub use canvas::{
+    Canvas,
+    columns
+};
+
+mod progress;
+pub use progress::{
+    BarFill,
+    MultiProgress,
+    ProgressBar,
+    ProgressHandle,
+    Spinner
+};
+
+mod tree;
+pub use tree::{
+    Tree,
+    TreeGuides,
+    TreeOptions
+};
+
+mod debug;
+#[cfg(not(feature = "us"))]
+pub use debug::{
+    DebugColoured,
+    debug_coloured
+};
+#[cfg(feature = "us")]
+pub use debug::{
+    DebugColoured as DebugColored,
+    debug_coloured as debug_colored
+};
+
+mod snapshot;
+pub use snapshot::{
+    RenderMode,
+    TagParseError,
+    snapshot_diff
+};
+
 mod functions;
 pub use functions::{
     style,
     fg,
-    bg
+    bg,
+    semantic
 };
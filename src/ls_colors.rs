@@ -0,0 +1,437 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs,
+    path::Path
+};
+#[cfg(unix)]
+use std::os::unix::fs::{
+    FileTypeExt,
+    MetadataExt,
+    PermissionsExt
+};
+
+use crate::{
+    consts::Formatting,
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// The `LS_COLORS` used by GNU `ls` when the variable is not set.
+const DEFAULT_LS_COLORS : &str = "rs=0:di=01;34:ln=01;36:mh=00:pi=40;33:so=01;35:do=01;35:bd=40;33;01:cd=40;33;01:or=40;31;01:mi=00:su=37;41:sg=30;43:ca=00:tw=30;42:ow=34;42:st=37;44:ex=01;32";
+
+/// The `dircolors` keywords and the `LS_COLORS` keys they set.
+const DIRCOLORS_KEYWORDS : [(&str, &str); 26] = [
+    ("NORMAL"                , "no"),
+    ("NORM"                  , "no"),
+    ("FILE"                  , "fi"),
+    ("RESET"                 , "rs"),
+    ("DIR"                   , "di"),
+    ("LINK"                  , "ln"),
+    ("LNK"                   , "ln"),
+    ("SYMLINK"               , "ln"),
+    ("MULTIHARDLINK"         , "mh"),
+    ("FIFO"                  , "pi"),
+    ("PIPE"                  , "pi"),
+    ("SOCK"                  , "so"),
+    ("DOOR"                  , "do"),
+    ("BLK"                   , "bd"),
+    ("BLOCK"                 , "bd"),
+    ("CHR"                   , "cd"),
+    ("CHAR"                  , "cd"),
+    ("ORPHAN"                , "or"),
+    ("MISSING"               , "mi"),
+    ("SETUID"                , "su"),
+    ("SETGID"                , "sg"),
+    ("CAPABILITY"            , "ca"),
+    ("STICKY_OTHER_WRITABLE" , "tw"),
+    ("OTHER_WRITABLE"        , "ow"),
+    ("STICKY"                , "st"),
+    ("EXEC"                  , "ex")
+];
+
+
+/// The kind of a file, as classified by `ls`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// A regular file.
+    File,
+    /// A directory.
+    Directory,
+    /// A directory which is sticky and writable by others.
+    StickyOtherWritable,
+    /// A directory which is writable by others.
+    OtherWritable,
+    /// A sticky directory.
+    Sticky,
+    /// A symbolic link.
+    Symlink,
+    /// A symbolic link whose target does not exist.
+    Orphan,
+    /// A path which does not exist.
+    Missing,
+    /// A named pipe.
+    Fifo,
+    /// A socket.
+    Socket,
+    /// A block device.
+    BlockDevice,
+    /// A character device.
+    CharDevice,
+    /// A regular file with the setuid bit.
+    Setuid,
+    /// A regular file with the setgid bit.
+    Setgid,
+    /// An executable regular file.
+    Executable,
+    /// A regular file with more than one hard link.
+    MultiHardLink
+}
+
+/// Classification
+impl FileKind {
+
+    /// Returns the `LS_COLORS` key of this kind.
+    pub fn key(&self) -> &'static str {
+        return match (self) {
+            FileKind::File                => "fi",
+            FileKind::Directory           => "di",
+            FileKind::StickyOtherWritable => "tw",
+            FileKind::OtherWritable       => "ow",
+            FileKind::Sticky              => "st",
+            FileKind::Symlink             => "ln",
+            FileKind::Orphan              => "or",
+            FileKind::Missing             => "mi",
+            FileKind::Fifo                => "pi",
+            FileKind::Socket              => "so",
+            FileKind::BlockDevice         => "bd",
+            FileKind::CharDevice          => "cd",
+            FileKind::Setuid              => "su",
+            FileKind::Setgid              => "sg",
+            FileKind::Executable          => "ex",
+            FileKind::MultiHardLink       => "mh"
+        };
+    }
+
+    /// Returns the kind to fall back to when this
+    /// kind has no style, if any.
+    fn fallback(&self) -> Option<FileKind> {
+        return match (self) {
+            FileKind::StickyOtherWritable
+            | FileKind::OtherWritable
+            | FileKind::Sticky        => Some(FileKind::Directory),
+            FileKind::Missing         => Some(FileKind::Orphan),
+            FileKind::Orphan          => Some(FileKind::Symlink),
+            FileKind::Setuid
+            | FileKind::Setgid        => Some(FileKind::Executable),
+            FileKind::Executable
+            | FileKind::MultiHardLink => Some(FileKind::File),
+            _                         => None
+        };
+    }
+
+    /// Classify a path using its metadata.
+    /// Symbolic links are not followed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::path::Path;
+    /// use vibrance::FileKind;
+    /// let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    /// assert_eq!(FileKind::of(root.join("src")), FileKind::Directory);
+    /// assert_eq!(FileKind::of(root.join("Cargo.toml")), FileKind::File);
+    /// assert_eq!(FileKind::of("/does/not/exist"), FileKind::Missing);
+    /// ```
+    pub fn of<P : AsRef<Path>>(path : P) -> FileKind {
+        let path = path.as_ref();
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return FileKind::Missing;
+        };
+        let file_type = metadata.file_type();
+        if (file_type.is_symlink()) {
+            return if (fs::metadata(path).is_ok()) {FileKind::Symlink} else {FileKind::Orphan};
+        }
+        if (file_type.is_dir()) {
+            return FileKind::of_directory(&metadata);
+        }
+        if (file_type.is_file()) {
+            return FileKind::of_file(&metadata);
+        }
+        return FileKind::of_special(&file_type).unwrap_or(FileKind::File);
+    }
+
+    /// Classify a directory using its permissions.
+    #[cfg(unix)]
+    fn of_directory(metadata : &fs::Metadata) -> FileKind {
+        let mode = metadata.permissions().mode();
+        return match (mode & 0o1000 != 0, mode & 0o002 != 0) {
+            (true,  true)  => FileKind::StickyOtherWritable,
+            (false, true)  => FileKind::OtherWritable,
+            (true,  false) => FileKind::Sticky,
+            (false, false) => FileKind::Directory
+        };
+    }
+    #[cfg(not(unix))]
+    fn of_directory(_ : &fs::Metadata) -> FileKind {
+        return FileKind::Directory;
+    }
+
+    /// Classify a regular file using its permissions.
+    #[cfg(unix)]
+    fn of_file(metadata : &fs::Metadata) -> FileKind {
+        let mode = metadata.permissions().mode();
+        return if (mode & 0o4000 != 0) {
+            FileKind::Setuid
+        } else if (mode & 0o2000 != 0) {
+            FileKind::Setgid
+        } else if (mode & 0o111 != 0) {
+            FileKind::Executable
+        } else if (metadata.nlink() > 1) {
+            FileKind::MultiHardLink
+        } else {
+            FileKind::File
+        };
+    }
+    #[cfg(not(unix))]
+    fn of_file(_ : &fs::Metadata) -> FileKind {
+        return FileKind::File;
+    }
+
+    /// Classify a file which is not a regular file, directory or link.
+    #[cfg(unix)]
+    fn of_special(file_type : &fs::FileType) -> Option<FileKind> {
+        return if (file_type.is_fifo()) {
+            Some(FileKind::Fifo)
+        } else if (file_type.is_socket()) {
+            Some(FileKind::Socket)
+        } else if (file_type.is_block_device()) {
+            Some(FileKind::BlockDevice)
+        } else if (file_type.is_char_device()) {
+            Some(FileKind::CharDevice)
+        } else {
+            None
+        };
+    }
+    #[cfg(not(unix))]
+    fn of_special(_ : &fs::FileType) -> Option<FileKind> {
+        return None;
+    }
+
+}
+
+
+/// Styles for paths, parsed from `LS_COLORS` or a `dircolors` database.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{FileKind, Formatting, LsColors};
+/// let colours = LsColors::parse("di=01;34:ln=target:*.tar=01;31:*.TAR=01;33");
+/// assert_eq!(colours.style_for_kind(FileKind::Directory).fg(), Some(Formatting::FgBlue));
+/// assert_eq!(colours.style_for_name("archive.tar", FileKind::File).fg(), Some(Formatting::FgRed));
+/// assert_eq!(colours.style_for_name("ARCHIVE.TAR", FileKind::File).fg(), Some(Formatting::FgYellow));
+/// 
+/// let s = colours.paint(env!("CARGO_MANIFEST_DIR"));
+/// assert!(s.style_at(0).is_bold());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LsColors {
+    kinds       : HashMap<String, Style>,
+    extensions  : Vec<(String, Style)>,
+    link_target : bool
+}
+
+/// Initialisation
+impl LsColors {
+
+    /// Create a new `LsColors` with no styles.
+    pub fn new() -> LsColors {
+        return LsColors {
+            kinds       : HashMap::new(),
+            extensions  : Vec::new(),
+            link_target : false
+        };
+    }
+
+    /// Parse the contents of an `LS_COLORS` variable,
+    /// such as `di=01;34:*.tar=01;31`.
+    /// Malformed entries are ignored.
+    pub fn parse(text : &str) -> LsColors {
+        let mut colours = LsColors::new();
+        for entry in text.split(':') {
+            if let Some((key, codes)) = entry.split_once('=') {
+                colours.set(key, codes);
+            }
+        }
+        return colours;
+    }
+
+    /// Parse a `dircolors` database, as printed by
+    /// `dircolors --print-database`.
+    /// `TERM`, `COLORTERM`, `COLOR`, `OPTIONS` and `EIGHTBIT`
+    /// lines are ignored, as are unknown keywords.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{FileKind, Formatting, LsColors};
+    /// let colours = LsColors::from_dircolors("
+    ///     # Comment
+    ///     TERM xterm*
+    ///     DIR 01;34 # directory
+    ///     EXEC 01;32
+    ///     .tar 01;31
+    ///     *README 04
+    ///     *#autosave# 35
+    /// ");
+    /// assert_eq!(colours.style_for_kind(FileKind::Executable).fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(colours.style_for_name("a.tar", FileKind::File).fg(), Some(Formatting::FgRed));
+    /// assert!(colours.style_for_name("README", FileKind::File).is_underline());
+    /// assert_eq!(colours.style_for_name("#autosave#", FileKind::File).fg(), Some(Formatting::FgMagenta));
+    /// ```
+    pub fn from_dircolors(text : &str) -> LsColors {
+        let mut colours = LsColors::new();
+        for line in text.lines() {
+            let comment = line.char_indices().find(|(idx, ch)| *ch == '#' && line[..*idx].chars().next_back().is_none_or(char::is_whitespace));
+            let line    = comment.map_or(line, |(idx, _)| &line[..idx]);
+            let mut words = line.split_whitespace();
+            let (Some(keyword), Some(codes)) = (words.next(), words.next()) else {
+                continue;
+            };
+            if (keyword.starts_with('.')) {
+                colours.set(&format!("*{}", keyword), codes);
+            } else if (keyword.starts_with('*')) {
+                colours.set(keyword, codes);
+            } else if let Some((_, key)) = DIRCOLORS_KEYWORDS.iter().find(|(other, _)| other.eq_ignore_ascii_case(keyword)) {
+                colours.set(key, codes);
+            }
+        }
+        return colours;
+    }
+
+    /// Parse the `LS_COLORS` environment variable, or use
+    /// the defaults of GNU `ls` if it is not set.
+    pub fn from_env() -> LsColors {
+        return match (env::var("LS_COLORS")) {
+            Ok(value) if ! value.is_empty() => LsColors::parse(&value),
+            _                               => LsColors::default()
+        };
+    }
+
+}
+
+/// The defaults of GNU `ls`.
+impl Default for LsColors {
+    fn default() -> LsColors {
+        return LsColors::parse(DEFAULT_LS_COLORS);
+    }
+}
+
+/// Styling
+impl LsColors {
+
+    /// Set the style of a key, such as `di` or `*.tar`.
+    /// 
+    /// # Internal
+    fn set(&mut self, key : &str, codes : &str) {
+        if (key == "ln" && codes == "target") {
+            self.link_target = true;
+            return;
+        }
+        let Some(formatting) = Formatting::parse_sgr(codes) else {
+            return;
+        };
+        let style = Style::from(formatting);
+        if let Some(suffix) = key.strip_prefix('*') {
+            self.extensions.retain(|(other, _)| other != suffix);
+            self.extensions.push((suffix.to_string(), style));
+        } else {
+            self.kinds.insert(key.to_string(), style);
+        }
+    }
+
+    /// Returns the style of a kind of file, ignoring extensions.
+    /// Kinds without a style fall back to a more general
+    /// kind, such as `ex` to `fi`.
+    pub fn style_for_kind(&self, kind : FileKind) -> Style {
+        let mut kind = Some(kind);
+        while let Some(next) = kind {
+            if let Some(style) = self.kinds.get(next.key()) {
+                return *style;
+            }
+            kind = next.fallback();
+        }
+        return self.kinds.get("no").copied().unwrap_or_default();
+    }
+
+    /// Returns the style of a file name of a given kind.
+    /// Extension globs only apply to [FileKind::File], and
+    /// case sensitive matches win over case insensitive ones.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{FileKind, Formatting, LsColors};
+    /// let colours = LsColors::parse("fi=33:mh=44:*.tar=01;31");
+    /// assert_eq!(colours.style_for_name("a.tar", FileKind::File).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(colours.style_for_name("a.tar", FileKind::MultiHardLink).fg(), None);
+    /// ```
+    pub fn style_for_name(&self, name : &str, kind : FileKind) -> Style {
+        if (kind == FileKind::File) {
+            let lower = name.to_lowercase();
+            let style = self.extensions.iter().rev().find(|(suffix, _)| name.ends_with(suffix.as_str()))
+                .or_else(|| self.extensions.iter().rev().find(|(suffix, _)| lower.ends_with(&suffix.to_lowercase())));
+            if let Some((_, style)) = style {
+                return *style;
+            }
+        }
+        return self.style_for_kind(kind);
+    }
+
+    /// Returns the style of a path, classifying it using
+    /// its metadata. Symbolic links use the style of their
+    /// target when `ln=target` is set. Like GNU `ls`, setuid,
+    /// setgid, executable and multiply linked files are
+    /// treated as regular files unless their key is set, so
+    /// extension globs still apply to them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use std::{fs, os::unix::fs::PermissionsExt};
+    /// use vibrance::{Formatting, LsColors};
+    /// let path = std::env::temp_dir().join(format!("vibrance-ls-colors-{}.sh", std::process::id()));
+    /// fs::write(&path, "").unwrap();
+    /// fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    /// assert_eq!(LsColors::parse("*.sh=32").style_for(&path).fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(LsColors::parse("ex=00:*.sh=32").style_for(&path).fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(LsColors::parse("ex=31:*.sh=32").style_for(&path).fg(), Some(Formatting::FgRed));
+    /// fs::remove_file(&path).unwrap();
+    /// # }
+    /// ```
+    pub fn style_for<P : AsRef<Path>>(&self, path : P) -> Style {
+        let     path = path.as_ref();
+        let mut kind = FileKind::of(path);
+        if (kind == FileKind::Symlink && self.link_target) {
+            kind = fs::canonicalize(path).map(FileKind::of).unwrap_or(FileKind::Orphan);
+        }
+        while (matches!(kind, FileKind::Setuid | FileKind::Setgid | FileKind::Executable | FileKind::MultiHardLink)
+            && self.kinds.get(kind.key()).is_none_or(|style| style.is_plain())
+        ) {
+            kind = kind.fallback().unwrap_or(FileKind::File);
+        }
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        return self.style_for_name(&name, kind);
+    }
+
+    /// Create a `ColouredString` of a path with its style applied.
+    /// See [LsColors::style_for].
+    pub fn paint<P : AsRef<Path>>(&self, path : P) -> ColouredString {
+        let path = path.as_ref();
+        return self.style_for(path).paint(path.display().to_string());
+    }
+
+}
@@ -0,0 +1,266 @@
+//! Support for the `cformat!` family of macros.
+//! 
+//! # Internal
+
+use std::fmt::Display;
+
+use crate::{
+    consts::FORMATTING_NAMES,
+    strings::ColouredString,
+    builder::ColouredStringBuilder
+};
+
+
+/// Create a `ColouredString` from a format string.
+/// 
+/// Style tags such as `{red}` or `{bold bg_blue}` format
+/// the text up to the matching `{/}`. Tag names match the
+/// formatting functions, with background colours prefixed
+/// by `bg_`. Each `{}` is replaced by the next argument,
+/// which can be a `ColouredString` or anything which
+/// implements `Display`. `{{` and `}}` insert literal braces.
+/// 
+/// Unknown style names, unbalanced tags and mismatched
+/// argument counts are compile errors.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{cformat, fg, Formatting};
+/// let s = cformat!("{red}error{/}: {bold}{} in {}{/}", fg::blue("E0001"), 3);
+/// assert_eq!(s.unformat(), "error: E0001 in 3");
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgRed));
+/// assert_eq!(s.style_at(7).fg(), Some(Formatting::FgBlue));
+/// assert!(s.style_at(7).is_bold());
+/// assert!(s.style_at(16).is_bold());
+/// ```
+/// 
+/// ```compile_fail
+/// vibrance::cformat!("{purple}text{/}");
+/// ```
+/// 
+/// ```compile_fail
+/// vibrance::cformat!("{red}text");
+/// ```
+/// 
+/// ```compile_fail
+/// vibrance::cformat!("{} and {}", 1);
+/// ```
+#[macro_export]
+macro_rules! cformat {
+    ($format:literal $(, $arg:expr)* $(,)?) => {{
+        const _ : () = $crate::macros::validate($format, <[&str]>::len(&[$(stringify!($arg)),*]));
+        #[allow(unused_imports)]
+        use $crate::macros::{ColouredArgument as _, DisplayArgument as _};
+        $crate::macros::format($format, vec![$((&$crate::macros::Argument(&$arg)).to_coloured()),*])
+    }};
+}
+
+/// Print a `cformat!` string to stdout.
+#[macro_export]
+macro_rules! cprint {
+    ($($arg:tt)*) => {
+        print!("{}", $crate::cformat!($($arg)*))
+    };
+}
+
+/// Print a `cformat!` string and a newline to stdout.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::cprintln;
+/// cprintln!("{green}ok{/}: {} tests passed", 12);
+/// ```
+#[macro_export]
+macro_rules! cprintln {
+    ($($arg:tt)*) => {
+        println!("{}", $crate::cformat!($($arg)*))
+    };
+}
+
+/// Print a `cformat!` string to stderr.
+#[macro_export]
+macro_rules! ceprint {
+    ($($arg:tt)*) => {
+        eprint!("{}", $crate::cformat!($($arg)*))
+    };
+}
+
+/// Print a `cformat!` string and a newline to stderr.
+#[macro_export]
+macro_rules! ceprintln {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::cformat!($($arg)*))
+    };
+}
+
+
+/// A `cformat!` argument.
+pub struct Argument<'l, T : ?Sized>(pub &'l T);
+
+/// Converts `ColouredString` arguments, keeping their formatting.
+/// Takes priority over `DisplayArgument`.
+pub trait ColouredArgument {
+    fn to_coloured(&self) -> ColouredString;
+}
+impl ColouredArgument for Argument<'_, ColouredString> {
+    fn to_coloured(&self) -> ColouredString {
+        return self.0.clone();
+    }
+}
+impl ColouredArgument for Argument<'_, &ColouredString> {
+    fn to_coloured(&self) -> ColouredString {
+        return (*self.0).clone();
+    }
+}
+
+/// Converts any other `Display` arguments.
+pub trait DisplayArgument {
+    fn to_coloured(&self) -> ColouredString;
+}
+impl<T : Display + ?Sized> DisplayArgument for &Argument<'_, T> {
+    fn to_coloured(&self) -> ColouredString {
+        return ColouredString::from(self.0.to_string());
+    }
+}
+
+
+pub use crate::debug::debug_coloured;
+
+/// The location written by `cdbg!`.
+pub fn debug_location(file : &str, line : u32, column : u32) -> ColouredString {
+    return ColouredString::from_semantic(format!("[{}:{}:{}]", file, line, column), "muted");
+}
+
+
+/// Returns `true` if the bytes between `start` and `end` are a formatting name.
+const fn is_formatting_name(bytes : &[u8], start : usize, end : usize) -> bool {
+    let mut i = 0;
+    while (i < FORMATTING_NAMES.len()) {
+        let name = FORMATTING_NAMES[i].0.as_bytes();
+        if (name.len() == end - start) {
+            let mut j = 0;
+            while (j < name.len() && name[j] == bytes[start + j]) {
+                j += 1;
+            }
+            if (j == name.len()) {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    return false;
+}
+
+/// Checks a `cformat!` format string at compile time.
+/// 
+/// # Panics
+/// 
+/// * The format string is malformed.
+/// * A style name is unknown.
+/// * A style tag is never closed, or closed without being opened.
+/// * The number of `{}` does not match the number of arguments.
+pub const fn validate(format : &str, arguments : usize) {
+    let     bytes        = format.as_bytes();
+    let mut i            = 0;
+    let mut depth        = 0;
+    let mut placeholders = 0;
+    while (i < bytes.len()) {
+        if (bytes[i] == b'{') {
+            if (i + 1 < bytes.len() && bytes[i + 1] == b'{') {
+                i += 2;
+                continue;
+            }
+            let     start = i + 1;
+            let mut end   = start;
+            while (end < bytes.len() && bytes[end] != b'}') {
+                if (bytes[end] == b'{') {
+                    panic!("cformat: `{{` inside a tag. Use `{{{{` to insert a literal brace.");
+                }
+                end += 1;
+            }
+            if (end == bytes.len()) {
+                panic!("cformat: Unclosed `{{`.");
+            }
+            if (start == end) {
+                placeholders += 1;
+            } else if (end == start + 1 && bytes[start] == b'/') {
+                if (depth == 0) {
+                    panic!("cformat: `{{/}}` without an open style tag.");
+                }
+                depth -= 1;
+            } else {
+                let mut word_start = start;
+                while (word_start < end) {
+                    let mut word_end = word_start;
+                    while (word_end < end && bytes[word_end] != b' ') {
+                        word_end += 1;
+                    }
+                    if (word_end > word_start && ! is_formatting_name(bytes, word_start, word_end)) {
+                        panic!("cformat: Unknown style name.");
+                    }
+                    word_start = word_end + 1;
+                }
+                depth += 1;
+            }
+            i = end + 1;
+        } else if (bytes[i] == b'}') {
+            if (i + 1 < bytes.len() && bytes[i + 1] == b'}') {
+                i += 2;
+            } else {
+                panic!("cformat: Unmatched `}}`. Use `}}}}` to insert a literal brace.");
+            }
+        } else {
+            i += 1;
+        }
+    }
+    if (depth != 0) {
+        panic!("cformat: Unclosed style tag.");
+    }
+    if (placeholders != arguments) {
+        panic!("cformat: The number of `{{}}` does not match the number of arguments.");
+    }
+}
+
+/// Builds a `ColouredString` from a validated `cformat!` format string.
+pub fn format(format : &str, arguments : Vec<ColouredString>) -> ColouredString {
+    let mut builder   = ColouredStringBuilder::new();
+    let mut arguments = arguments.into_iter();
+    let mut opened    = Vec::new();
+    let mut text      = String::new();
+    let mut chars     = format.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (ch) {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            },
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            },
+            '{' => {
+                builder.text(std::mem::take(&mut text));
+                let tag = chars.by_ref().take_while(|&ch| ch != '}').collect::<String>();
+                if (tag.is_empty()) {
+                    builder.text(arguments.next().expect("Argument count is validated."));
+                } else if (tag == "/") {
+                    for _ in 0..opened.pop().expect("Tags are validated.") {
+                        builder.pop_style();
+                    }
+                } else {
+                    let names = tag.split(' ').filter(|name| ! name.is_empty()).collect::<Vec<&str>>();
+                    for name in &names {
+                        let (_, formatting) = FORMATTING_NAMES.iter().find(|(other, _)| other == name).expect("Names are validated.");
+                        builder.push_style(*formatting);
+                    }
+                    opened.push(names.len());
+                }
+            },
+            _ => text.push(ch)
+        }
+    }
+    builder.text(text);
+    return builder.build();
+}
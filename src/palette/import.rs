@@ -0,0 +1,525 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    }
+};
+
+use crate::{
+    json,
+    palette::{
+        Palette,
+        Rgb
+    }
+};
+
+
+/// The names of the colours in a Windows Terminal scheme.
+const WINDOWS_TERMINAL_NAMES : [&str; 16] = [
+    "black", "red", "green", "yellow", "blue", "purple", "cyan", "white",
+    "brightBlack", "brightRed", "brightGreen", "brightYellow", "brightBlue", "brightPurple", "brightCyan", "brightWhite"
+];
+
+/// The base16 colour used for each of the 16 named colours,
+/// followed by the foreground, background and cursor.
+const BASE16_NAMES : [&str; 19] = [
+    "base00", "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base05",
+    "base03", "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base07",
+    "base05", "base00", "base05"
+];
+
+
+/// An error produced when a colour scheme can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteParseError {
+    /// The line of the error, starting at 1.
+    pub line    : usize,
+    /// A description of the error.
+    pub message : String
+}
+
+impl Display for PaletteParseError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}: {}", self.line, self.message);
+    }
+}
+
+impl Error for PaletteParseError { }
+
+
+/// Returns the line number of a byte offset.
+fn line_of(text : &str, offset : usize) -> usize {
+    return text[..offset.min(text.len())].matches('\n').count() + 1;
+}
+
+/// Parses an X11 colour, such as `#ff8800` or `rgb:ff/88/00`.
+fn parse_x_colour(value : &str) -> Option<Rgb> {
+    if let Some(channels) = value.strip_prefix("rgb:") {
+        let channels = channels.split('/').map(|channel| {
+            if (channel.is_empty() || channel.len() > 4) {
+                return None;
+            }
+            let max = (1u32 << (channel.len() * 4)) - 1;
+            return u32::from_str_radix(channel, 16).ok().map(|n| ((n * 255 + max / 2) / max) as u8);
+        }).collect::<Option<Vec<u8>>>()?;
+        return match (channels.as_slice()) {
+            &[r, g, b] => Some(Rgb::new(r, g, b)),
+            _          => None
+        };
+    }
+    return value.starts_with('#').then(|| Rgb::from_hex(value)).flatten();
+}
+
+/// Sets a colour of a palette by its common name.
+/// Returns `false` if the name is not a palette colour.
+fn set_colour(palette : &mut Palette, name : &str, colour : Rgb) -> bool {
+    if let Some(n) = name.strip_prefix("color").and_then(|n| n.parse::<usize>().ok()) {
+        if (n < 16) {
+            palette.ansi[n] = colour;
+            return true;
+        }
+        return false;
+    }
+    match (name) {
+        "foreground"                  => palette.foreground = colour,
+        "background"                  => palette.background = colour,
+        "cursor" | "cursorColor"
+        | "cursorcolor"               => palette.cursor = colour,
+        _                             => return false
+    }
+    return true;
+}
+
+/// Returns `true` if the name is a palette colour.
+fn is_colour_name(name : &str) -> bool {
+    return set_colour(&mut Palette::default(), name, Rgb::default());
+}
+
+
+/// Imports
+impl Palette {
+
+    /// Parse the colours of an Xresources file, such as
+    /// `*.color1: #cc0000` or `URxvt.foreground: rgb:e5/e5/e5`.
+    /// `#define` substitutions are supported. Colours which
+    /// are not set keep their value from the xterm palette.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Palette, Rgb};
+    /// let palette = Palette::from_xresources("
+    ///     ! Comment
+    ///     #define red #cc0000
+    ///     *.color1:       red
+    ///     *foreground:    rgb:ff/ff/ff
+    ///     URxvt*color12:  #5c5cff
+    /// ").unwrap();
+    /// assert_eq!(palette.ansi[1], Rgb::new(204, 0, 0));
+    /// assert_eq!(palette.ansi[12], Rgb::new(92, 92, 255));
+    /// assert_eq!(palette.foreground, Rgb::new(255, 255, 255));
+    /// ```
+    pub fn from_xresources(text : &str) -> Result<Palette, PaletteParseError> {
+        let mut palette = Palette::default();
+        let mut defines = HashMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if let Some(define) = line.strip_prefix("#define") {
+                let mut words = define.split_whitespace();
+                if let (Some(name), Some(value)) = (words.next(), words.next()) {
+                    defines.insert(name.to_string(), value.to_string());
+                }
+                continue;
+            }
+            if (line.is_empty() || line.starts_with('!') || line.starts_with('#')) {
+                continue;
+            }
+            let Some((resource, value)) = line.split_once(':') else {
+                continue;
+            };
+            let name  = resource.rsplit(['.', '*']).next().unwrap_or_default().trim();
+            let value = value.trim();
+            let value = defines.get(value).map_or(value, |value| value.as_str());
+            if (is_colour_name(name)) {
+                let colour = parse_x_colour(value).ok_or_else(|| PaletteParseError {
+                    line    : idx + 1,
+                    message : format!("Invalid colour `{}` for `{}`.", value, name)
+                })?;
+                set_colour(&mut palette, name, colour);
+            }
+        }
+        return Ok(palette);
+    }
+
+    /// Parse the colours of a kitty config file, such as
+    /// `color1 #cc0000` or `background #000000`.
+    /// Colours which are not set keep their value from
+    /// the xterm palette.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Palette, Rgb};
+    /// let palette = Palette::from_kitty("
+    ///     # Comment
+    ///     font_size 12.0
+    ///     color1     #cc0000
+    ///     background #101010
+    ///     cursor     none
+    /// ").unwrap();
+    /// assert_eq!(palette.ansi[1], Rgb::new(204, 0, 0));
+    /// assert_eq!(palette.background, Rgb::new(16, 16, 16));
+    /// ```
+    pub fn from_kitty(text : &str) -> Result<Palette, PaletteParseError> {
+        let mut palette = Palette::default();
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if (line.is_empty() || line.starts_with('#')) {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let (Some(name), Some(value)) = (words.next(), words.next()) else {
+                continue;
+            };
+            if (! is_colour_name(name) || value == "none") {
+                continue;
+            }
+            let colour = parse_x_colour(value).ok_or_else(|| PaletteParseError {
+                line    : idx + 1,
+                message : format!("Invalid colour `{}` for `{}`.", value, name)
+            })?;
+            set_colour(&mut palette, name, colour);
+        }
+        return Ok(palette);
+    }
+
+    /// Parse a base16 scheme, such as `base08: "ab4642"`.
+    /// The colours are mapped to the named colours the same
+    /// way as base16-shell. Every colour used by the mapping
+    /// must be set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Palette, Rgb};
+    /// let palette = Palette::from_base16("
+    ///     scheme: \"Default Dark\"
+    ///     base00: \"181818\"
+    ///     base01: \"282828\"
+    ///     base02: \"383838\"
+    ///     base03: \"585858\"
+    ///     base04: \"b8b8b8\"
+    ///     base05: \"d8d8d8\"
+    ///     base06: \"e8e8e8\"
+    ///     base07: \"f8f8f8\"
+    ///     base08: \"ab4642\" # red
+    ///     base09: \"dc9656\"
+    ///     base0A: \"f7ca88\"
+    ///     base0B: \"a1b56c\"
+    ///     base0C: \"86c1b9\"
+    ///     base0D: \"7cafc2\"
+    ///     base0E: \"ba8baf\"
+    ///     base0F: \"a16946\"
+    /// ").unwrap();
+    /// assert_eq!(palette.ansi[1], Rgb::new(0xab, 0x46, 0x42));
+    /// assert_eq!(palette.background, Rgb::new(0x18, 0x18, 0x18));
+    /// assert!(Palette::from_base16("base00: \"181818\"").is_err());
+    /// ```
+    pub fn from_base16(text : &str) -> Result<Palette, PaletteParseError> {
+        let mut colours = HashMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            let Some((key, value)) = line.trim().split_once(':') else {
+                continue;
+            };
+            let key = key.trim().trim_matches(['"', '\'']);
+            if (! (key.len() == 6 && key.starts_with("base"))) {
+                continue;
+            }
+            let value = value.trim();
+            let value = match (value.chars().next()) {
+                Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+                _                          => value.split(" #").next().unwrap_or_default().trim()
+            };
+            let colour = Rgb::from_hex(value).ok_or_else(|| PaletteParseError {
+                line    : idx + 1,
+                message : format!("Invalid colour `{}` for `{}`.", value, key)
+            })?;
+            colours.insert(key.to_lowercase(), colour);
+        }
+        let mut resolved = Vec::new();
+        for name in BASE16_NAMES {
+            resolved.push(*colours.get(&name.to_lowercase()).ok_or_else(|| PaletteParseError {
+                line    : line_of(text, text.len()),
+                message : format!("Missing colour `{}`.", name)
+            })?);
+        }
+        let mut palette = Palette::default();
+        palette.ansi.copy_from_slice(&resolved[..16]);
+        palette.foreground = resolved[16];
+        palette.background = resolved[17];
+        palette.cursor     = resolved[18];
+        return Ok(palette);
+    }
+
+    /// Parse a Windows Terminal colour scheme, or the first
+    /// scheme of a Windows Terminal `settings.json`.
+    /// Colours which are not set keep their value from
+    /// the xterm palette.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Palette, Rgb};
+    /// let palette = Palette::from_windows_terminal(r##"{
+    ///     "name": "Example",
+    ///     "purple": "#881798",
+    ///     "brightBlue": "#3B78FF",
+    ///     "cursorColor": "#FFFFFF"
+    /// }"##).unwrap();
+    /// assert_eq!(palette.ansi[5], Rgb::new(0x88, 0x17, 0x98));
+    /// assert_eq!(palette.ansi[12], Rgb::new(0x3b, 0x78, 0xff));
+    /// assert_eq!(palette.cursor, Rgb::new(255, 255, 255));
+    /// assert_eq!(Palette::from_windows_terminal("{\n\"red\": 1\n}").unwrap_err().line, 2);
+    /// 
+    /// let settings = "{\"schemes\": [\n{\"name\": \"red\",\n\"red\": \"#FF0000\",\n\"blue\": 3}\n]}";
+    /// assert_eq!(Palette::from_windows_terminal(settings).unwrap_err().line, 4);
+    /// ```
+    pub fn from_windows_terminal(text : &str) -> Result<Palette, PaletteParseError> {
+        let (document, offsets) = json::parse_with_offsets(text).map_err(|(offset, message)| PaletteParseError {
+            line : line_of(text, offset),
+            message
+        })?;
+        // The index of the value of each entry of an object in `offsets`.
+        let indices = |entries : &[(String, json::Value)], start : usize| entries.iter().scan(start + 1, |index, (_, value)| {
+            let current = *index;
+            *index += value.count();
+            return Some(current);
+        }).collect::<Vec<usize>>();
+        let mut scheme = &document;
+        let mut start  = 0;
+        if let json::Value::Object(entries) = &document {
+            if let Some(n) = entries.iter().position(|(key, value)| key == "schemes" && matches!(value, json::Value::Array(_))) {
+                let index = indices(entries, 0)[n];
+                scheme = entries[n].1.as_array().and_then(<[json::Value]>::first).ok_or_else(|| PaletteParseError {
+                    line    : line_of(text, offsets[index]),
+                    message : String::from("No colour schemes found.")
+                })?;
+                start  = index + 1;
+            }
+        }
+        let json::Value::Object(entries) = scheme else {
+            return Err(PaletteParseError {
+                line    : 1,
+                message : String::from("Expected a colour scheme object.")
+            });
+        };
+        let mut palette = Palette::default();
+        for ((key, value), index) in entries.iter().zip(indices(entries, start)) {
+            let name = match (WINDOWS_TERMINAL_NAMES.iter().position(|name| name == key)) {
+                Some(n)                  => format!("color{}", n),
+                None if is_colour_name(key) => key.clone(),
+                None                     => continue
+            };
+            let colour = value.as_str().and_then(Rgb::from_hex).ok_or_else(|| PaletteParseError {
+                line    : line_of(text, offsets[index]),
+                message : format!("Invalid colour for `{}`.", key)
+            })?;
+            set_colour(&mut palette, &name, colour);
+        }
+        return Ok(palette);
+    }
+
+    /// Parse an iTerm2 `.itermcolors` file.
+    /// Colours which are not set keep their value from
+    /// the xterm palette.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Palette, Rgb};
+    /// let palette = Palette::from_iterm(r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+    /// <plist version="1.0">
+    /// <dict>
+    ///     <key>Ansi 1 Color</key>
+    ///     <dict>
+    ///         <key>Alpha Component</key>
+    ///         <real>1</real>
+    ///         <key>Blue Component</key>
+    ///         <real>0.0</real>
+    ///         <key>Color Space</key>
+    ///         <string>sRGB</string>
+    ///         <key>Green Component</key>
+    ///         <real>0.5</real>
+    ///         <key>Red Component</key>
+    ///         <real>1</real>
+    ///     </dict>
+    ///     <key>Background Color</key>
+    ///     <dict>
+    ///         <key>Blue Component</key><real>0.2</real>
+    ///         <key>Green Component</key><real>0.2</real>
+    ///         <key>Red Component</key><real>0.2</real>
+    ///     </dict>
+    /// </dict>
+    /// </plist>"#).unwrap();
+    /// assert_eq!(palette.ansi[1], Rgb::new(255, 128, 0));
+    /// assert_eq!(palette.background, Rgb::new(51, 51, 51));
+    ///
+    /// let nested = format!("<plist>{}", "<array>".repeat(100_000));
+    /// assert_eq!(Palette::from_iterm(&nested).unwrap_err().message, "Too deeply nested.");
+    /// ```
+    pub fn from_iterm(text : &str) -> Result<Palette, PaletteParseError> {
+        let mut parser  = PlistParser { text, offset : 0, depth : 0 };
+        let     root    = parser.document()?;
+        let mut palette = Palette::default();
+        let Plist::Dict(entries) = root else {
+            return Err(parser.error("Expected a `<dict>`."));
+        };
+        for (key, value) in entries {
+            let name = if let Some(n) = key.strip_prefix("Ansi ").and_then(|rest| rest.strip_suffix(" Color")) {
+                format!("color{}", n)
+            } else {
+                match (key.as_str()) {
+                    "Foreground Color" => String::from("foreground"),
+                    "Background Color" => String::from("background"),
+                    "Cursor Color"     => String::from("cursor"),
+                    _                  => continue
+                }
+            };
+            let Plist::Dict(components) = value else {
+                continue;
+            };
+            let component = |name : &str| components.iter().find(|(key, _)| key == name).and_then(|(_, value)| match (value) {
+                Plist::Real(n) => Some((n.clamp(0.0, 1.0) * 255.0).round() as u8),
+                _              => None
+            }).unwrap_or(0);
+            set_colour(&mut palette, &name, Rgb::new(component("Red Component"), component("Green Component"), component("Blue Component")));
+        }
+        return Ok(palette);
+    }
+
+}
+
+
+/// The most dicts and arrays which may be nested in a property list.
+const MAX_PLIST_DEPTH : usize = 128;
+
+/// A property list value.
+enum Plist {
+    Dict(Vec<(String, Plist)>),
+    Real(f64),
+    Other
+}
+
+/// The state of a property list parser.
+struct PlistParser<'l> {
+    text   : &'l str,
+    offset : usize,
+    /// The number of dicts and arrays currently open.
+    depth  : usize
+}
+
+impl PlistParser<'_> {
+
+    fn error(&self, message : &str) -> PaletteParseError {
+        return PaletteParseError {
+            line    : line_of(self.text, self.offset),
+            message : String::from(message)
+        };
+    }
+
+    /// Returns the next tag, skipping text, comments and declarations.
+    /// The tag is returned without its brackets and attributes.
+    fn tag(&mut self) -> Result<String, PaletteParseError> {
+        loop {
+            let start = self.text[self.offset..].find('<').ok_or_else(|| self.error("Unexpected end of file."))?;
+            self.offset += start;
+            if (self.text[self.offset..].starts_with("<!--")) {
+                let end = self.text[self.offset..].find("-->").ok_or_else(|| self.error("Unterminated comment."))?;
+                self.offset += end + 3;
+                continue;
+            }
+            let end = self.text[self.offset..].find('>').ok_or_else(|| self.error("Unterminated tag."))?;
+            let tag = &self.text[self.offset + 1..self.offset + end];
+            self.offset += end + 1;
+            if (tag.starts_with('?') || tag.starts_with('!')) {
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            return Ok(if (tag.ends_with('/') && ! name.ends_with('/')) {format!("{}/", name)} else {name.to_string()});
+        }
+    }
+
+    /// Returns the text up to the given closing tag.
+    fn text_until(&mut self, name : &str) -> Result<String, PaletteParseError> {
+        let close = format!("</{}>", name);
+        let end   = self.text[self.offset..].find(&close).ok_or_else(|| self.error(&format!("Expected `{}`.", close)))?;
+        let text  = self.text[self.offset..self.offset + end].trim().to_string();
+        self.offset += end + close.len();
+        return Ok(text);
+    }
+
+    fn document(&mut self) -> Result<Plist, PaletteParseError> {
+        let tag = self.tag()?;
+        if (tag != "plist") {
+            return Err(self.error("Expected `<plist>`."));
+        }
+        let tag = self.tag()?;
+        return self.value(&tag);
+    }
+
+    fn value(&mut self, tag : &str) -> Result<Plist, PaletteParseError> {
+        let nested = matches!(tag, "dict" | "array");
+        if (nested) {
+            if (self.depth >= MAX_PLIST_DEPTH) {
+                return Err(self.error("Too deeply nested."));
+            }
+            self.depth += 1;
+        }
+        let value = match (tag) {
+            "dict" => {
+                let mut entries = Vec::new();
+                loop {
+                    match (self.tag()?.as_str()) {
+                        "/dict" => break,
+                        "key"   => {
+                            let key   = self.text_until("key")?;
+                            let tag   = self.tag()?;
+                            entries.push((key, self.value(&tag)?));
+                        },
+                        _ => return Err(self.error("Expected `<key>`."))
+                    }
+                }
+                Ok(Plist::Dict(entries))
+            },
+            "array" => {
+                loop {
+                    let tag = self.tag()?;
+                    if (tag == "/array") {
+                        break;
+                    }
+                    self.value(&tag)?;
+                }
+                Ok(Plist::Other)
+            },
+            "real" | "integer" => {
+                let text = self.text_until(tag)?;
+                text.parse::<f64>().map(Plist::Real).map_err(|_| self.error(&format!("Invalid number `{}`.", text)))
+            },
+            "string" | "data" | "date" => {
+                self.text_until(tag)?;
+                Ok(Plist::Other)
+            },
+            "true/" | "false/" | "string/" => Ok(Plist::Other),
+            _ => Err(self.error(&format!("Unexpected `<{}>`.", tag)))
+        };
+        if (nested) {
+            self.depth -= 1;
+        }
+        return value;
+    }
+
+}
@@ -0,0 +1,306 @@
+use std::fmt::{
+    self,
+    Display,
+    Formatter
+};
+
+use crate::{
+    consts::Formatting,
+    styles::Style
+};
+
+mod import;
+pub use import::PaletteParseError;
+
+
+/// A 24 bit colour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r : u8,
+    pub g : u8,
+    pub b : u8
+}
+
+/// Initialisation
+impl Rgb {
+    /// Create a new `Rgb` from its channels.
+    pub const fn new(r : u8, g : u8, b : u8) -> Rgb {
+        return Rgb { r, g, b };
+    }
+
+    /// Create a new `Rgb` from a `u32` of the form `0xRRGGBB`.
+    pub const fn from_u32(rgb : u32) -> Rgb {
+        return Rgb::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8);
+    }
+
+    /// Parse a hex colour, such as `#ff8800`, `ff8800` or `#f80`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::Rgb;
+    /// assert_eq!(Rgb::from_hex("#ff8800"), Some(Rgb::new(255, 136, 0)));
+    /// assert_eq!(Rgb::from_hex("f80"), Some(Rgb::new(255, 136, 0)));
+    /// assert_eq!(Rgb::from_hex("#ff88"), None);
+    /// ```
+    pub fn from_hex(hex : &str) -> Option<Rgb> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if (! hex.bytes().all(|byte| byte.is_ascii_hexdigit())) {
+            return None;
+        }
+        return match (hex.len()) {
+            3 => {
+                let channel = |idx : usize| u8::from_str_radix(&hex[idx..idx + 1], 16).ok().map(|n| n * 17);
+                Some(Rgb::new(channel(0)?, channel(1)?, channel(2)?))
+            },
+            6 => u32::from_str_radix(hex, 16).ok().map(Rgb::from_u32),
+            _ => None
+        };
+    }
+
+    /// Returns the colour as a hex code, such as `#ff8800`.
+    pub fn to_hex(&self) -> String {
+        return format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b);
+    }
+}
+
+/// Writes the hex code of the colour.
+impl Display for Rgb {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.to_hex());
+    }
+}
+
+
+/// The names of the built-in palettes.
+const BUILTIN_NAMES : [&str; 8] = [
+    "xterm",
+    "vga",
+    "campbell",
+    "tango",
+    "solarized_dark",
+    "solarized_light",
+    "dracula",
+    "gruvbox_dark"
+];
+
+
+/// The concrete colours a terminal uses to display
+/// the named colours of `Formatting`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{Formatting, Palette, Rgb};
+/// let palette = Palette::vga();
+/// assert_eq!(palette.resolve(&Formatting::FgRed), Some(Rgb::new(170, 0, 0)));
+/// assert_eq!(palette.resolve(&Formatting::BgBrightBlue), Some(Rgb::new(85, 85, 255)));
+/// assert_eq!(palette.resolve(&Formatting::Fg8Bit(196)), Some(Rgb::new(255, 0, 0)));
+/// assert_eq!(palette.resolve(&Formatting::Bold), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    /// The 16 named colours, in the order black, red, green,
+    /// yellow, blue, magenta, cyan, white, then the bright
+    /// variants in the same order.
+    pub ansi       : [Rgb; 16],
+    /// The default foreground colour.
+    pub foreground : Rgb,
+    /// The default background colour.
+    pub background : Rgb,
+    /// The cursor colour.
+    pub cursor     : Rgb
+}
+
+/// Initialisation
+impl Palette {
+
+    /// Create a new `Palette` from 16 colours of the
+    /// form `0xRRGGBB`, and the foreground, background
+    /// and cursor colours.
+    /// 
+    /// # Internal
+    const fn from_u32(ansi : [u32; 16], foreground : u32, background : u32, cursor : u32) -> Palette {
+        let mut colours = [Rgb::new(0, 0, 0); 16];
+        let mut i       = 0;
+        while (i < 16) {
+            colours[i] = Rgb::from_u32(ansi[i]);
+            i += 1;
+        }
+        return Palette {
+            ansi       : colours,
+            foreground : Rgb::from_u32(foreground),
+            background : Rgb::from_u32(background),
+            cursor     : Rgb::from_u32(cursor)
+        };
+    }
+
+    /// The default colours of xterm.
+    pub const fn xterm() -> Palette {
+        return Palette::from_u32([
+            0x000000, 0xcd0000, 0x00cd00, 0xcdcd00, 0x0000ee, 0xcd00cd, 0x00cdcd, 0xe5e5e5,
+            0x7f7f7f, 0xff0000, 0x00ff00, 0xffff00, 0x5c5cff, 0xff00ff, 0x00ffff, 0xffffff
+        ], 0xe5e5e5, 0x000000, 0xe5e5e5);
+    }
+
+    /// The colours of the VGA text mode.
+    pub const fn vga() -> Palette {
+        return Palette::from_u32([
+            0x000000, 0xaa0000, 0x00aa00, 0xaa5500, 0x0000aa, 0xaa00aa, 0x00aaaa, 0xaaaaaa,
+            0x555555, 0xff5555, 0x55ff55, 0xffff55, 0x5555ff, 0xff55ff, 0x55ffff, 0xffffff
+        ], 0xaaaaaa, 0x000000, 0xaaaaaa);
+    }
+
+    /// The Campbell scheme, the default of Windows Terminal.
+    pub const fn campbell() -> Palette {
+        return Palette::from_u32([
+            0x0c0c0c, 0xc50f1f, 0x13a10e, 0xc19c00, 0x0037da, 0x881798, 0x3a96dd, 0xcccccc,
+            0x767676, 0xe74856, 0x16c60c, 0xf9f1a5, 0x3b78ff, 0xb4009e, 0x61d6d6, 0xf2f2f2
+        ], 0xcccccc, 0x0c0c0c, 0xffffff);
+    }
+
+    /// The Tango scheme, used by GNOME Terminal.
+    pub const fn tango() -> Palette {
+        return Palette::from_u32([
+            0x2e3436, 0xcc0000, 0x4e9a06, 0xc4a000, 0x3465a4, 0x75507b, 0x06989a, 0xd3d7cf,
+            0x555753, 0xef2929, 0x8ae234, 0xfce94f, 0x729fcf, 0xad7fa8, 0x34e2e2, 0xeeeeec
+        ], 0xd3d7cf, 0x2e3436, 0xd3d7cf);
+    }
+
+    /// The dark variant of the Solarized scheme.
+    pub const fn solarized_dark() -> Palette {
+        return Palette::from_u32([
+            0x073642, 0xdc322f, 0x859900, 0xb58900, 0x268bd2, 0xd33682, 0x2aa198, 0xeee8d5,
+            0x002b36, 0xcb4b16, 0x586e75, 0x657b83, 0x839496, 0x6c71c4, 0x93a1a1, 0xfdf6e3
+        ], 0x839496, 0x002b36, 0x93a1a1);
+    }
+
+    /// The light variant of the Solarized scheme.
+    pub const fn solarized_light() -> Palette {
+        return Palette::from_u32([
+            0x073642, 0xdc322f, 0x859900, 0xb58900, 0x268bd2, 0xd33682, 0x2aa198, 0xeee8d5,
+            0x002b36, 0xcb4b16, 0x586e75, 0x657b83, 0x839496, 0x6c71c4, 0x93a1a1, 0xfdf6e3
+        ], 0x657b83, 0xfdf6e3, 0x586e75);
+    }
+
+    /// The Dracula scheme.
+    pub const fn dracula() -> Palette {
+        return Palette::from_u32([
+            0x21222c, 0xff5555, 0x50fa7b, 0xf1fa8c, 0xbd93f9, 0xff79c6, 0x8be9fd, 0xf8f8f2,
+            0x6272a4, 0xff6e6e, 0x69ff94, 0xffffa5, 0xd6acff, 0xff92df, 0xa4ffff, 0xffffff
+        ], 0xf8f8f2, 0x282a36, 0xf8f8f2);
+    }
+
+    /// The dark variant of the Gruvbox scheme.
+    pub const fn gruvbox_dark() -> Palette {
+        return Palette::from_u32([
+            0x282828, 0xcc241d, 0x98971a, 0xd79921, 0x458588, 0xb16286, 0x689d6a, 0xa89984,
+            0x928374, 0xfb4934, 0xb8bb26, 0xfabd2f, 0x83a598, 0xd3869b, 0x8ec07c, 0xebdbb2
+        ], 0xebdbb2, 0x282828, 0xebdbb2);
+    }
+
+    /// Returns the names of the built-in palettes.
+    /// See [Palette::builtin].
+    pub fn builtin_names() -> &'static [&'static str] {
+        return &BUILTIN_NAMES;
+    }
+
+    /// Returns a built-in palette by name, such as
+    /// `xterm` or `solarized_dark`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::Palette;
+    /// for name in Palette::builtin_names() {
+    ///     assert!(Palette::builtin(name).is_some());
+    /// }
+    /// assert_eq!(Palette::builtin("dracula"), Some(Palette::dracula()));
+    /// ```
+    pub fn builtin(name : &str) -> Option<Palette> {
+        return Some(match (name) {
+            "xterm"           => Palette::xterm(),
+            "vga"             => Palette::vga(),
+            "campbell"        => Palette::campbell(),
+            "tango"           => Palette::tango(),
+            "solarized_dark"  => Palette::solarized_dark(),
+            "solarized_light" => Palette::solarized_light(),
+            "dracula"         => Palette::dracula(),
+            "gruvbox_dark"    => Palette::gruvbox_dark(),
+            _                 => return None
+        });
+    }
+
+}
+
+/// The xterm palette.
+impl Default for Palette {
+    fn default() -> Palette {
+        return Palette::xterm();
+    }
+}
+
+/// Resolution
+impl Palette {
+
+    /// Returns the colour of an 8 bit colour index.
+    /// Indices 0 to 15 use the named colours of this
+    /// palette, 16 to 231 a 6x6x6 colour cube and
+    /// 232 to 255 a greyscale ramp.
+    pub fn colour_8bit(&self, n : u8) -> Rgb {
+        return match (n) {
+            0..=15    => self.ansi[n as usize],
+            16..=231  => {
+                let level = |value : u8| if (value == 0) {0} else {value * 40 + 55};
+                let n     = n - 16;
+                Rgb::new(level(n / 36), level((n / 6) % 6), level(n % 6))
+            },
+            232..=255 => {
+                let level = (n - 232) * 10 + 8;
+                Rgb::new(level, level, level)
+            }
+        };
+    }
+
+    /// Returns the colour a formatting variant sets, or `None`
+    /// if it does not set a colour. `FgReset` and `BgReset`
+    /// resolve to the default foreground and background.
+    pub fn resolve(&self, formatting : &Formatting) -> Option<Rgb> {
+        return Some(match (formatting) {
+            Formatting::FgBlack         | Formatting::BgBlack         => self.ansi[0],
+            Formatting::FgRed           | Formatting::BgRed           => self.ansi[1],
+            Formatting::FgGreen         | Formatting::BgGreen         => self.ansi[2],
+            Formatting::FgYellow        | Formatting::BgYellow        => self.ansi[3],
+            Formatting::FgBlue          | Formatting::BgBlue          => self.ansi[4],
+            Formatting::FgMagenta       | Formatting::BgMagenta       => self.ansi[5],
+            Formatting::FgCyan          | Formatting::BgCyan          => self.ansi[6],
+            Formatting::FgWhite         | Formatting::BgWhite         => self.ansi[7],
+            Formatting::FgBrightBlack   | Formatting::BgBrightBlack   => self.ansi[8],
+            Formatting::FgBrightRed     | Formatting::BgBrightRed     => self.ansi[9],
+            Formatting::FgBrightGreen   | Formatting::BgBrightGreen   => self.ansi[10],
+            Formatting::FgBrightYellow  | Formatting::BgBrightYellow  => self.ansi[11],
+            Formatting::FgBrightBlue    | Formatting::BgBrightBlue    => self.ansi[12],
+            Formatting::FgBrightMagenta | Formatting::BgBrightMagenta => self.ansi[13],
+            Formatting::FgBrightCyan    | Formatting::BgBrightCyan    => self.ansi[14],
+            Formatting::FgBrightWhite   | Formatting::BgBrightWhite   => self.ansi[15],
+            Formatting::Fg8Bit(n)       | Formatting::Bg8Bit(n)       => self.colour_8bit(*n),
+            Formatting::Fg24Bit(r, g, b) | Formatting::Bg24Bit(r, g, b) => Rgb::new(*r, *g, *b),
+            Formatting::FgReset => self.foreground,
+            Formatting::BgReset => self.background,
+            _                   => return None
+        });
+    }
+
+    /// Returns the foreground colour of a `Style`,
+    /// ignoring whether it is inverted.
+    pub fn foreground_of(&self, style : &Style) -> Rgb {
+        return style.fg().and_then(|formatting| self.resolve(&formatting)).unwrap_or(self.foreground);
+    }
+
+    /// Returns the background colour of a `Style`,
+    /// ignoring whether it is inverted.
+    pub fn background_of(&self, style : &Style) -> Rgb {
+        return style.bg().and_then(|formatting| self.resolve(&formatting)).unwrap_or(self.background);
+    }
+
+}
@@ -0,0 +1,264 @@
+use crate::{
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// The characters used to draw the border of a [Panel].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Border {
+    /// `┌─┐│└┘`
+    #[default]
+    Single,
+    /// `╔═╗║╚╝`
+    Double,
+    /// `╭─╮│╰╯`
+    Rounded,
+    /// `┏━┓┃┗┛`
+    Heavy,
+    /// `+-+|++`
+    Ascii
+}
+
+/// Getters
+impl Border {
+
+    /// Returns the top left, top right, bottom left and bottom
+    /// right corners, then the horizontal and vertical edges.
+    pub fn parts(&self) -> [char; 6] {
+        return match (self) {
+            Border::Single  => ['┌', '┐', '└', '┘', '─', '│'],
+            Border::Double  => ['╔', '╗', '╚', '╝', '═', '║'],
+            Border::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            Border::Heavy   => ['┏', '┓', '┗', '┛', '━', '┃'],
+            Border::Ascii   => ['+', '+', '+', '+', '-', '|']
+        };
+    }
+
+}
+
+
+/// Where the title or footer of a [Panel] is placed in its border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    #[cfg(not(feature = "us"))]
+    Centre,
+    #[cfg(feature = "us")]
+    Center,
+    Right
+}
+
+
+/// Content framed by a border, with an optional title and footer.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{fg, Alignment, Border, Formatting, Panel, Style};
+/// let panel = Panel::new("Build finished\nin 2.5s")
+///     .with_title(fg::green("ok"))
+///     .with_footer("vibrance")
+///     .with_footer_alignment(Alignment::Right)
+///     .with_border(Border::Rounded)
+///     .with_border_style(Style::from(Formatting::FgBlue));
+/// let s = panel.render();
+/// assert_eq!(s.unformat(), [
+///     "╭─ ok ───────────╮\n",
+///     "│ Build finished │\n",
+///     "│ in 2.5s        │\n",
+///     "╰───── vibrance ─╯\n"
+/// ].concat());
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgBlue));
+/// assert_eq!(s.style_at(8).fg(), Some(Formatting::FgGreen));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Panel {
+    content          : ColouredString,
+    title            : Option<ColouredString>,
+    footer           : Option<ColouredString>,
+    border           : Border,
+    border_style     : Style,
+    title_alignment  : Alignment,
+    footer_alignment : Alignment,
+    padding          : (usize, usize),
+    width            : Option<usize>
+}
+
+/// Initialisation
+impl Panel {
+
+    /// Create a new `Panel` around some content, with a single
+    /// border and one column of padding on each side.
+    pub fn new<S : Into<ColouredString>>(content : S) -> Panel {
+        return Panel {
+            content          : content.into(),
+            title            : None,
+            footer           : None,
+            border           : Border::Single,
+            border_style     : Style::new(),
+            title_alignment  : Alignment::Left,
+            footer_alignment : Alignment::Left,
+            padding          : (1, 0),
+            width            : None
+        };
+    }
+
+    /// Returns this `Panel` with a title in its top border.
+    pub fn with_title<S : Into<ColouredString>>(mut self, title : S) -> Panel {
+        self.title = Some(title.into());
+        return self;
+    }
+
+    /// Returns this `Panel` with a footer in its bottom border.
+    pub fn with_footer<S : Into<ColouredString>>(mut self, footer : S) -> Panel {
+        self.footer = Some(footer.into());
+        return self;
+    }
+
+    /// Returns this `Panel` with the given border characters.
+    pub fn with_border(mut self, border : Border) -> Panel {
+        self.border = border;
+        return self;
+    }
+
+    /// Returns this `Panel` with the given border style.
+    pub fn with_border_style(mut self, style : Style) -> Panel {
+        self.border_style = style;
+        return self;
+    }
+
+    /// Returns this `Panel` with its title aligned.
+    pub fn with_title_alignment(mut self, alignment : Alignment) -> Panel {
+        self.title_alignment = alignment;
+        return self;
+    }
+
+    /// Returns this `Panel` with its footer aligned.
+    pub fn with_footer_alignment(mut self, alignment : Alignment) -> Panel {
+        self.footer_alignment = alignment;
+        return self;
+    }
+
+    /// Returns this `Panel` with the given number of blank
+    /// columns left and right of the content, and blank
+    /// lines above and below it.
+    pub fn with_padding(mut self, horizontal : usize, vertical : usize) -> Panel {
+        self.padding = (horizontal, vertical);
+        return self;
+    }
+
+    /// Returns this `Panel` with a fixed width, including the
+    /// border. Lines of content which are too long are cut off.
+    /// If `None`, the panel fits its content.
+    pub fn with_width(mut self, width : Option<usize>) -> Panel {
+        self.width = width;
+        return self;
+    }
+
+}
+
+/// Rendering
+impl Panel {
+
+    /// Render this `Panel`, one line at a time.
+    ///
+    /// The widths of the content, title and footer are
+    /// measured in terminal columns, so styled text and wide
+    /// characters line up. Tabs are expanded to the next
+    /// multiple of 8 columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{Alignment, Border, Panel};
+    /// let panel = Panel::new("日本語 text that is long")
+    ///     .with_title("Title")
+    ///     .with_title_alignment(Alignment::Centre)
+    ///     .with_border(Border::Ascii)
+    ///     .with_padding(2, 1)
+    ///     .with_width(Some(20));
+    /// assert_eq!(panel.render().unformat(), [
+    ///     "+----- Title ------+\n",
+    ///     "|                  |\n",
+    ///     "|  日本語 text th  |\n",
+    ///     "|                  |\n",
+    ///     "+------------------+\n"
+    /// ].concat());
+    ///
+    /// let panel = Panel::new("a\tb\n\tc").with_border(Border::Ascii);
+    /// assert_eq!(panel.render().unformat(), [
+    ///     "+-----------+\n",
+    ///     "| a       b |\n",
+    ///     "|         c |\n",
+    ///     "+-----------+\n"
+    /// ].concat());
+    /// ```
+    pub fn render(&self) -> ColouredString {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = self.border.parts();
+        let (padding_x, padding_y) = self.padding;
+        let lines  = self.content.expand_tabs(8).lines();
+        let title  = self.title.as_ref().map(|title| title.expand_tabs(8));
+        let footer = self.footer.as_ref().map(|footer| footer.expand_tabs(8));
+
+        let span = match (self.width) {
+            Some(width) => width.saturating_sub(2),
+            None        => {
+                let content = lines.iter().map(|line| line.width()).max().unwrap_or(0) + padding_x * 2;
+                let labels  = [&title, &footer].into_iter().flatten().map(|label| label.width() + 4).max().unwrap_or(0);
+                content.max(labels)
+            }
+        };
+        let inner = span.saturating_sub(padding_x * 2);
+        let left  = padding_x.min(span);
+
+        let mut result = ColouredString::new();
+        result.push(self.edge(top_left, top_right, horizontal, title.as_ref(), self.title_alignment, span));
+        let side  = self.border_style.paint(vertical.to_string());
+        let blank = side.clone() + " ".repeat(span) + side.clone() + "\n";
+        for _ in 0..padding_y {
+            result.push(blank.clone());
+        }
+        for line in lines {
+            let mut line = line.clone();
+            line.truncate_width(inner);
+            let fill = span - left - line.width();
+            result.push(side.clone() + " ".repeat(left) + line + " ".repeat(fill) + side.clone() + "\n");
+        }
+        for _ in 0..padding_y {
+            result.push(blank.clone());
+        }
+        result.push(self.edge(bottom_left, bottom_right, horizontal, footer.as_ref(), self.footer_alignment, span));
+        return result;
+    }
+
+    /// Render the top or bottom border, with a label
+    /// placed in it.
+    ///
+    /// # Internal
+    fn edge(&self, left : char, right : char, horizontal : char, label : Option<&ColouredString>, alignment : Alignment, span : usize) -> ColouredString {
+        let line = |length : usize| self.border_style.paint(horizontal.to_string().repeat(length));
+        let mut result = self.border_style.paint(left.to_string());
+        match (label.filter(|_| span >= 5)) {
+            Some(label) => {
+                let mut label = label.clone();
+                label.truncate_width(span - 4);
+                let rest   = span - label.width() - 2;
+                let before = match (alignment) {
+                    Alignment::Left   => 1,
+                    #[cfg(not(feature = "us"))]
+                    Alignment::Centre => rest / 2,
+                    #[cfg(feature = "us")]
+                    Alignment::Center => rest / 2,
+                    Alignment::Right  => rest - 1
+                };
+                result.push(line(before) + " " + label + " " + line(rest - before));
+            },
+            None => result.push(line(span))
+        }
+        result.push(self.border_style.paint(right.to_string()) + "\n");
+        return result;
+    }
+
+}
@@ -0,0 +1,181 @@
+use crate::{
+    builder::ColouredStringBuilder,
+    consts::Formatting,
+    json::{
+        self,
+        Value
+    },
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// Options for [Value::pretty].
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{Formatting, PrettyOptions, Style};
+/// let options = PrettyOptions {
+///     indent    : 4,
+///     key       : Style::from(Formatting::FgMagenta),
+///     max_items : Some(10),
+///     ..PrettyOptions::default()
+/// };
+/// assert_eq!(options.max_depth, None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrettyOptions {
+    /// The number of spaces per level of nesting.
+    pub indent      : usize,
+    /// Object keys.
+    pub key         : Style,
+    /// String values.
+    pub string      : Style,
+    /// Number values.
+    pub number      : Style,
+    /// `true`, `false` and `null`.
+    pub constant    : Style,
+    /// Brackets, braces, commas and colons.
+    pub punctuation : Style,
+    /// Notes about hidden values, such as `… 5 more`.
+    pub ellipsis    : Style,
+    /// The number of values of an array which are shown
+    /// before the rest are collapsed. If `None`, every
+    /// value is shown.
+    pub max_items   : Option<usize>,
+    /// The depth below which arrays and objects are
+    /// collapsed. If `None`, every level is shown.
+    pub max_depth   : Option<usize>
+}
+
+/// Initialisation
+impl PrettyOptions {
+
+    /// Create new `PrettyOptions` with the default values.
+    pub fn new() -> PrettyOptions {
+        return PrettyOptions::default();
+    }
+
+}
+
+impl Default for PrettyOptions {
+    fn default() -> PrettyOptions {
+        return PrettyOptions {
+            indent      : 2,
+            key         : Style::from(Formatting::FgBlue),
+            string      : Style::from(Formatting::FgGreen),
+            number      : Style::from(Formatting::FgCyan),
+            constant    : Style::from(Formatting::FgMagenta),
+            punctuation : Style::new(),
+            ellipsis    : Style::from(Formatting::Faint),
+            max_items   : None,
+            max_depth   : None
+        };
+    }
+}
+
+
+/// Pretty printing
+impl Value {
+
+    /// Write this `Value` as indented JSON with colour.
+    ///
+    /// Empty arrays and objects are written as `[]` and `{}`.
+    /// Arrays longer than `max_items` end with a note such as
+    /// `… 5 more`, and arrays and objects deeper than `max_depth`
+    /// are written as `[…]` and `{…}`. Numbers which are not
+    /// finite are written as `null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{Formatting, PrettyOptions, Value};
+    /// let value = Value::parse(r#"{"name": "vibrance", "tags": [1, 2, 3], "meta": {"a": null}}"#).unwrap();
+    /// let s = value.pretty(&PrettyOptions::default());
+    /// assert_eq!(s.unformat(), "{\n  \"name\": \"vibrance\",\n  \"tags\": [\n    1,\n    2,\n    3\n  ],\n  \"meta\": {\n    \"a\": null\n  }\n}");
+    /// assert_eq!(s.style_at(4).fg(), Some(Formatting::FgBlue));
+    /// assert_eq!(s.style_at(12).fg(), Some(Formatting::FgGreen));
+    ///
+    /// let options = PrettyOptions { max_items : Some(1), ..PrettyOptions::default() };
+    /// assert!(value.pretty(&options).unformat().contains("\"tags\": [\n    1,\n    … 2 more\n  ],"));
+    /// let options = PrettyOptions { max_depth : Some(1), ..PrettyOptions::default() };
+    /// assert!(value.pretty(&options).unformat().ends_with("\"tags\": […],\n  \"meta\": {…}\n}"));
+    /// ```
+    pub fn pretty(&self, options : &PrettyOptions) -> ColouredString {
+        let mut result = ColouredStringBuilder::new();
+        self.pretty_into(&mut result, options, 0);
+        return result.build();
+    }
+
+    /// Write this `Value` to the end of a `ColouredStringBuilder`.
+    ///
+    /// # Internal
+    fn pretty_into(&self, result : &mut ColouredStringBuilder, options : &PrettyOptions, depth : usize) {
+        let collapsed = options.max_depth.is_some_and(|max_depth| depth >= max_depth);
+        let newline   = |result : &mut ColouredStringBuilder, depth : usize| {
+            result.text(format!("\n{}", " ".repeat(depth * options.indent)));
+        };
+        match (self) {
+
+            Value::Null        => { result.text(options.constant.paint("null")); },
+            Value::Bool(value) => { result.text(options.constant.paint(value.to_string())); },
+
+            Value::Number(number) if number.is_finite() => { result.text(options.number.paint(number.to_string())); },
+            Value::Number(_)                            => { result.text(options.constant.paint("null")); },
+
+            Value::String(string) => { result.text(options.string.paint(json::quote(string))); },
+
+            Value::Array(values) if values.is_empty() => { result.text(options.punctuation.paint("[]")); },
+            Value::Array(_) if collapsed => {
+                result.text(options.punctuation.paint("["));
+                result.text(options.ellipsis.paint("…"));
+                result.text(options.punctuation.paint("]"));
+            },
+            Value::Array(values) => {
+                let shown = options.max_items.map_or(values.len(), |max_items| max_items.min(values.len()));
+                result.text(options.punctuation.paint("["));
+                for (idx, value) in values[..shown].iter().enumerate() {
+                    if (idx > 0) {
+                        result.text(options.punctuation.paint(","));
+                    }
+                    newline(result, depth + 1);
+                    value.pretty_into(result, options, depth + 1);
+                }
+                if (shown < values.len()) {
+                    if (shown > 0) {
+                        result.text(options.punctuation.paint(","));
+                    }
+                    newline(result, depth + 1);
+                    result.text(options.ellipsis.paint(format!("… {} more", values.len() - shown)));
+                }
+                newline(result, depth);
+                result.text(options.punctuation.paint("]"));
+            },
+
+            Value::Object(entries) if entries.is_empty() => { result.text(options.punctuation.paint("{}")); },
+            Value::Object(_) if collapsed => {
+                result.text(options.punctuation.paint("{"));
+                result.text(options.ellipsis.paint("…"));
+                result.text(options.punctuation.paint("}"));
+            },
+            Value::Object(entries) => {
+                result.text(options.punctuation.paint("{"));
+                for (idx, (key, value)) in entries.iter().enumerate() {
+                    if (idx > 0) {
+                        result.text(options.punctuation.paint(","));
+                    }
+                    newline(result, depth + 1);
+                    result.text(options.key.paint(json::quote(key)));
+                    result.text(options.punctuation.paint(":"));
+                    result.text(" ");
+                    value.pretty_into(result, options, depth + 1);
+                }
+                newline(result, depth);
+                result.text(options.punctuation.paint("}"));
+            }
+
+        }
+    }
+
+}
@@ -0,0 +1,679 @@
+use std::{
+    io::{
+        self,
+        Write
+    },
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError
+    },
+    time::Instant
+};
+
+use crate::{
+    builder::ColouredStringBuilder,
+    consts::Formatting,
+    palette::Rgb,
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// The eighth blocks used by [BarFill::Blocks], from empty to full.
+const EIGHTHS : [char; 9] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// The frames of a [Spinner] by default.
+const SPINNER_FRAMES : [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+
+/// A clock which starts when it is created, or which
+/// only moves when it is advanced.
+///
+/// # Internal
+#[derive(Debug, Clone)]
+struct Clock {
+    /// `None` if the clock only moves with [Clock::advance].
+    start  : Option<Instant>,
+    offset : f64
+}
+
+impl Clock {
+
+    fn new(scripted : bool) -> Clock {
+        return Clock {
+            start  : (! scripted).then(Instant::now),
+            offset : 0.0
+        };
+    }
+
+    fn time(&self) -> f64 {
+        return self.offset + self.start.map_or(0.0, |start| start.elapsed().as_secs_f64());
+    }
+
+    fn advance(&mut self, seconds : f64) {
+        self.offset += seconds.max(0.0);
+    }
+
+}
+
+
+/// Writes a number of seconds as `M:SS`, or `H:MM:SS` if
+/// it is an hour or longer.
+fn duration(seconds : f64) -> String {
+    let seconds = seconds.max(0.0).round() as u64;
+    return if (seconds >= 3600) {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    };
+}
+
+/// Replaces the `{name}` placeholders of a template. Values
+/// take the style of the placeholder, and placeholders with
+/// no value are kept as they are.
+fn fill_template<F : Fn(&str) -> Option<ColouredString>>(template : &ColouredString, value : F) -> ColouredString {
+    let text  = template.unformat();
+    let chars = template.styled_chars();
+    let paint = |text : ColouredString, style : Style| if (style.is_plain()) {text} else {style.paint(text)};
+
+    let mut result = ColouredStringBuilder::new();
+    let mut offset = 0;
+    let mut idx    = 0;
+    while (idx < chars.len()) {
+        let style       = chars[idx].1;
+        let placeholder = text[offset..].strip_prefix('{')
+            .and_then(|rest| rest.find('}').map(|end| &rest[..end]))
+            .and_then(|name| value(name).map(|value| (name, value)));
+        if let Some((name, value)) = placeholder {
+            result.text(paint(value, style));
+            offset += name.len() + 2;
+            idx    += name.chars().count() + 2;
+            continue;
+        }
+        let mut end = idx + 1;
+        while (end < chars.len() && chars[end].1 == style && chars[end].0 != '{') {
+            end += 1;
+        }
+        let run = chars[idx..end].iter().map(|(ch, _)| *ch).collect::<String>();
+        offset += run.len();
+        idx     = end;
+        result.text(paint(ColouredString::from(run), style));
+    }
+    return result.build();
+}
+
+/// Interpolates between two colours.
+fn mix(from : Rgb, to : Rgb, amount : f64) -> Rgb {
+    let channel = |from : u8, to : u8| (from as f64 + (to as f64 - from as f64) * amount).round() as u8;
+    return Rgb::new(channel(from.r, to.r), channel(from.g, to.g), channel(from.b, to.b));
+}
+
+/// Writes a line over the current line of a terminal.
+fn draw_line<W : Write>(out : &mut W, line : &ColouredString) -> io::Result<()> {
+    write!(out, "\r{}\x1b[K", line.format())?;
+    return out.flush();
+}
+
+
+/// How the bar of a [ProgressBar] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BarFill {
+    /// Whole cells of one character, then another.
+    Chars {
+        filled : char,
+        empty  : char
+    },
+    /// Eighth blocks such as `▌`, which show progress
+    /// smaller than a cell.
+    Blocks,
+    /// Cells of `█` which blend from one colour to another
+    /// along the bar, then cells of `░`.
+    Gradient(Rgb, Rgb)
+}
+
+
+/// A progress bar drawn from a template.
+///
+/// The template is a `ColouredString` with placeholders,
+/// which are replaced by values in the style of the placeholder:
+///
+/// * `{bar}` - The bar.
+/// * `{pos}` and `{len}` - The position and length.
+/// * `{percent}` - The percentage done, without a `%`.
+/// * `{elapsed}` - The time since the bar was created.
+/// * `{eta}` - The estimated time left, or `-:--` before
+///   there is any progress.
+/// * `{msg}` - The message.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{fg, BarFill, Formatting, ProgressBar, Style};
+/// let mut bar = ProgressBar::new_scripted(10)
+///     .with_template("[" + fg::cyan("{bar}") + "] {pos}/{len} {eta} {msg}")
+///     .with_width(8)
+///     .with_fill(BarFill::Chars { filled : '#', empty : '-' });
+/// bar.set_message("copying");
+/// bar.inc(4);
+/// bar.advance_clock(8.0);
+/// let s = bar.render();
+/// assert_eq!(s.unformat(), "[###-----] 4/10 0:12 copying");
+/// assert_eq!(s.style_at(1).fg(), Some(Formatting::FgCyan));
+///
+/// bar.inc(100);
+/// assert_eq!(bar.render().unformat(), "[########] 10/10 0:00 copying");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProgressBar {
+    template     : ColouredString,
+    position     : u64,
+    length       : u64,
+    width        : usize,
+    fill         : BarFill,
+    filled_style : Style,
+    empty_style  : Style,
+    message      : String,
+    clock        : Clock
+}
+
+/// Initialisation
+impl ProgressBar {
+
+    /// Create a new `ProgressBar` of the given length,
+    /// whose clock starts now.
+    pub fn new(length : u64) -> ProgressBar {
+        let mut bar = ProgressBar::new_scripted(length);
+        bar.clock = Clock::new(false);
+        return bar;
+    }
+
+    /// Create a new `ProgressBar` of the given length, whose
+    /// clock only moves with [ProgressBar::advance_clock].
+    pub fn new_scripted(length : u64) -> ProgressBar {
+        return ProgressBar {
+            template     : ColouredString::from("[{bar}] {pos}/{len} {eta}"),
+            position     : 0,
+            length,
+            width        : 30,
+            fill         : BarFill::Blocks,
+            filled_style : Style::new(),
+            empty_style  : Style::from(Formatting::Faint),
+            message      : String::new(),
+            clock        : Clock::new(true)
+        };
+    }
+
+    /// Returns this `ProgressBar` with the given template.
+    pub fn with_template<S : Into<ColouredString>>(mut self, template : S) -> ProgressBar {
+        self.template = template.into();
+        return self;
+    }
+
+    /// Returns this `ProgressBar` with a bar of the given number of columns.
+    pub fn with_width(mut self, width : usize) -> ProgressBar {
+        self.width = width;
+        return self;
+    }
+
+    /// Returns this `ProgressBar` with the given way of drawing the bar.
+    pub fn with_fill(mut self, fill : BarFill) -> ProgressBar {
+        self.fill = fill;
+        return self;
+    }
+
+    /// Returns this `ProgressBar` with the given styles for
+    /// the filled and empty parts of the bar.
+    pub fn with_styles(mut self, filled : Style, empty : Style) -> ProgressBar {
+        self.filled_style = filled;
+        self.empty_style  = empty;
+        return self;
+    }
+
+}
+
+/// Getters
+impl ProgressBar {
+
+    /// Returns the position.
+    pub fn position(&self) -> u64 {
+        return self.position;
+    }
+
+    /// Returns the length.
+    pub fn length(&self) -> u64 {
+        return self.length;
+    }
+
+    /// Returns `true` if the position has reached the length.
+    pub fn is_finished(&self) -> bool {
+        return self.position >= self.length;
+    }
+
+    /// Returns the number of seconds since the bar was created.
+    pub fn elapsed(&self) -> f64 {
+        return self.clock.time();
+    }
+
+    /// Returns the estimated number of seconds left, if
+    /// there has been any progress.
+    pub fn eta(&self) -> Option<f64> {
+        if (self.position == 0) {
+            return None;
+        }
+        let left = self.length.saturating_sub(self.position) as f64;
+        return Some(self.elapsed() / self.position as f64 * left);
+    }
+
+    /// Returns the part of the bar which is done, from `0.0` to `1.0`.
+    fn ratio(&self) -> f64 {
+        return if (self.length == 0) {1.0} else {(self.position as f64 / self.length as f64).min(1.0)};
+    }
+
+}
+
+/// Progress
+impl ProgressBar {
+
+    /// Move the position forward, up to the length.
+    pub fn inc(&mut self, delta : u64) {
+        self.set_position(self.position.saturating_add(delta));
+    }
+
+    /// Set the position, up to the length.
+    pub fn set_position(&mut self, position : u64) {
+        self.position = position.min(self.length);
+    }
+
+    /// Set the length.
+    pub fn set_length(&mut self, length : u64) {
+        self.length   = length;
+        self.position = self.position.min(length);
+    }
+
+    /// Set the message shown by `{msg}`.
+    pub fn set_message<S : Into<String>>(&mut self, message : S) {
+        self.message = message.into();
+    }
+
+    /// Move the position to the length.
+    pub fn finish(&mut self) {
+        self.position = self.length;
+    }
+
+    /// Move the clock forward.
+    pub fn advance_clock(&mut self, seconds : f64) {
+        self.clock.advance(seconds);
+    }
+
+}
+
+/// Rendering
+impl ProgressBar {
+
+    /// Render the bar on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{BarFill, Formatting, ProgressBar, Rgb};
+    /// let mut bar = ProgressBar::new_scripted(16).with_width(4);
+    /// bar.inc(5);
+    /// assert_eq!(bar.bar().unformat(), "█▎  ");
+    ///
+    /// let bar = bar.with_fill(BarFill::Gradient(Rgb::new(255, 0, 0), Rgb::new(0, 0, 255)));
+    /// let s   = bar.bar();
+    /// assert_eq!(s.unformat(), "█░░░");
+    /// assert_eq!(s.style_at(0).fg(), Some(Formatting::Fg24Bit(255, 0, 0)));
+    /// ```
+    pub fn bar(&self) -> ColouredString {
+        let ratio = self.ratio();
+        let (filled, empty) = match (self.fill) {
+            BarFill::Chars { filled, empty } => {
+                let cells = (ratio * self.width as f64).floor() as usize;
+                (self.filled_style.paint(filled.to_string().repeat(cells)), empty.to_string().repeat(self.width - cells))
+            },
+            BarFill::Blocks => {
+                let eighths = (ratio * self.width as f64 * 8.0).round() as usize;
+                let partial    = EIGHTHS[eighths % 8];
+                let mut filled = EIGHTHS[8].to_string().repeat(eighths / 8);
+                if (partial != EIGHTHS[0]) {
+                    filled.push(partial);
+                }
+                let cells = filled.chars().count();
+                (self.filled_style.paint(filled), EIGHTHS[0].to_string().repeat(self.width - cells))
+            },
+            BarFill::Gradient(from, to) => {
+                let cells = (ratio * self.width as f64).floor() as usize;
+                let mut filled = ColouredStringBuilder::new();
+                for cell in 0..cells {
+                    let Rgb { r, g, b } = mix(from, to, cell as f64 / (self.width.max(2) - 1) as f64);
+                    filled.text(self.filled_style.with(Formatting::Fg24Bit(r, g, b)).paint("█"));
+                }
+                (filled.build(), String::from("░").repeat(self.width - cells))
+            }
+        };
+        return filled + self.empty_style.paint(empty);
+    }
+
+    /// Render the template.
+    pub fn render(&self) -> ColouredString {
+        return fill_template(&self.template, |name| match (name) {
+            "bar"     => Some(self.bar()),
+            "pos"     => Some(ColouredString::from(self.position.to_string())),
+            "len"     => Some(ColouredString::from(self.length.to_string())),
+            "percent" => Some(ColouredString::from(((self.ratio() * 100.0).floor() as u64).to_string())),
+            "elapsed" => Some(ColouredString::from(duration(self.elapsed()))),
+            "eta"     => Some(ColouredString::from(self.eta().map_or(String::from("-:--"), duration))),
+            "msg"     => Some(ColouredString::from(self.message.as_str())),
+            _         => None
+        });
+    }
+
+    /// Draw the template over the current line, by writing
+    /// a carriage return, the template and an erase sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{fg, ProgressBar};
+    /// let mut bar = ProgressBar::new_scripted(4).with_template(fg::green("{pos}") + "/{len}");
+    /// bar.inc(3);
+    /// let mut out = Vec::new();
+    /// bar.draw(&mut out).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "\r\x1b[32m3\x1b[0m/\x1b[0m4\x1b[0m\x1b[K");
+    /// ```
+    pub fn draw<W : Write>(&self, out : &mut W) -> io::Result<()> {
+        return draw_line(out, &self.render());
+    }
+
+}
+
+
+/// A spinner drawn from a template, for work of unknown length.
+///
+/// The template has the placeholders `{spinner}`, `{elapsed}`
+/// and `{msg}`. See [ProgressBar].
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{fg, Formatting, Spinner};
+/// let mut spinner = Spinner::new_scripted()
+///     .with_template(fg::yellow("{spinner}") + " {msg} ({elapsed})")
+///     .with_frames(&["-", "\\", "|", "/"]);
+/// spinner.set_message("waiting");
+/// spinner.advance_clock(75.0);
+/// assert_eq!(spinner.render().unformat(), "- waiting (1:15)");
+/// spinner.tick();
+/// let s = spinner.render();
+/// assert_eq!(s.unformat(), "\\ waiting (1:15)");
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgYellow));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    template : ColouredString,
+    frames   : Vec<String>,
+    frame    : usize,
+    message  : String,
+    clock    : Clock
+}
+
+/// Initialisation
+impl Spinner {
+
+    /// Create a new `Spinner` whose clock starts now.
+    pub fn new() -> Spinner {
+        let mut spinner = Spinner::new_scripted();
+        spinner.clock = Clock::new(false);
+        return spinner;
+    }
+
+    /// Create a new `Spinner` whose clock only
+    /// moves with [Spinner::advance_clock].
+    pub fn new_scripted() -> Spinner {
+        return Spinner {
+            template : ColouredString::from("{spinner} {msg}"),
+            frames   : SPINNER_FRAMES.iter().map(|frame| frame.to_string()).collect(),
+            frame    : 0,
+            message  : String::new(),
+            clock    : Clock::new(true)
+        };
+    }
+
+    /// Returns this `Spinner` with the given template.
+    pub fn with_template<S : Into<ColouredString>>(mut self, template : S) -> Spinner {
+        self.template = template.into();
+        return self;
+    }
+
+    /// Returns this `Spinner` with the given frames, which
+    /// are shown in turn.
+    ///
+    /// # Panics
+    /// Panics if `frames` is empty.
+    pub fn with_frames(mut self, frames : &[&str]) -> Spinner {
+        assert!(! frames.is_empty(), "A spinner needs at least one frame.");
+        self.frames = frames.iter().map(|frame| frame.to_string()).collect();
+        self.frame  = 0;
+        return self;
+    }
+
+}
+
+impl Default for Spinner {
+    fn default() -> Spinner {
+        return Spinner::new();
+    }
+}
+
+/// Progress
+impl Spinner {
+
+    /// Move to the next frame.
+    pub fn tick(&mut self) {
+        self.frame = (self.frame + 1) % self.frames.len();
+    }
+
+    /// Set the message shown by `{msg}`.
+    pub fn set_message<S : Into<String>>(&mut self, message : S) {
+        self.message = message.into();
+    }
+
+    /// Move the clock forward.
+    pub fn advance_clock(&mut self, seconds : f64) {
+        self.clock.advance(seconds);
+    }
+
+}
+
+/// Rendering
+impl Spinner {
+
+    /// Render the template.
+    pub fn render(&self) -> ColouredString {
+        return fill_template(&self.template, |name| match (name) {
+            "spinner" => Some(ColouredString::from(self.frames[self.frame].as_str())),
+            "elapsed" => Some(ColouredString::from(duration(self.clock.time()))),
+            "msg"     => Some(ColouredString::from(self.message.as_str())),
+            _         => None
+        });
+    }
+
+    /// Draw the template over the current line.
+    /// See [ProgressBar::draw].
+    pub fn draw<W : Write>(&self, out : &mut W) -> io::Result<()> {
+        return draw_line(out, &self.render());
+    }
+
+}
+
+
+/// Draws several lines of progress at once, such as
+/// the bars of tasks which run at the same time.
+///
+/// Each redraw moves the cursor up to the first line with
+/// a cursor-up sequence, then writes every line over the
+/// last, so the output can be captured and checked with a
+/// [Terminal](crate::Terminal).
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{BarFill, MultiProgress, ProgressBar, Spinner, Terminal};
+/// let mut a = ProgressBar::new_scripted(4).with_width(4).with_fill(BarFill::Chars { filled : '=', empty : ' ' });
+/// let mut b = a.clone();
+/// let mut s = Spinner::new_scripted().with_frames(&["."]);
+/// s.set_message("working");
+///
+/// let mut multi = MultiProgress::new(Vec::new());
+/// multi.draw([a.render(), b.render(), s.render()]).unwrap();
+/// a.inc(1);
+/// b.inc(3);
+/// multi.draw([a.render(), b.render(), s.render()]).unwrap();
+/// multi.finish().unwrap();
+///
+/// let mut terminal = Terminal::new(20, 5);
+/// terminal.feed(&String::from_utf8(multi.into_inner()).unwrap());
+/// assert_eq!(terminal.text(), "[=   ] 1/4 0:00\n[=== ] 3/4 0:00\n. working");
+/// assert_eq!(terminal.cursor(), (3, 0));
+/// ```
+///
+/// Bars can also be added, then updated from other
+/// threads through their handles while one thread redraws.
+///
+/// ```
+/// use std::thread;
+/// use vibrance::{MultiProgress, ProgressBar, Terminal};
+/// let mut multi = MultiProgress::new(Vec::new());
+/// let handles = (0..2).map(|_| multi.add(ProgressBar::new_scripted(8).with_template("{pos}/{len}"))).collect::<Vec<_>>();
+/// thread::scope(|scope| {
+///     for (i, handle) in handles.iter().enumerate() {
+///         let handle = handle.clone();
+///         scope.spawn(move || handle.inc(4 * (i as u64 + 1)));
+///     }
+/// });
+/// multi.redraw().unwrap();
+///
+/// let mut terminal = Terminal::new(20, 5);
+/// terminal.feed(&String::from_utf8(multi.into_inner()).unwrap());
+/// assert_eq!(terminal.text(), "4/8\n8/8");
+/// ```
+#[derive(Debug)]
+pub struct MultiProgress<W : Write> {
+    writer : W,
+    lines  : usize,
+    bars   : Vec<ProgressHandle>
+}
+
+/// Initialisation
+impl<W : Write> MultiProgress<W> {
+
+    /// Create a new `MultiProgress` which draws to a writer.
+    pub fn new(writer : W) -> MultiProgress<W> {
+        return MultiProgress {
+            writer,
+            lines : 0,
+            bars  : Vec::new()
+        };
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        return self.writer;
+    }
+
+}
+
+/// Drawing
+impl<W : Write> MultiProgress<W> {
+
+    /// Draw some lines over the lines drawn last time.
+    /// Any lines left over from last time are erased.
+    pub fn draw<I : IntoIterator<Item = ColouredString>>(&mut self, lines : I) -> io::Result<()> {
+        if (self.lines > 1) {
+            write!(self.writer, "\x1b[{}A", self.lines - 1)?;
+        }
+        self.lines = 0;
+        for line in lines {
+            if (self.lines > 0) {
+                writeln!(self.writer)?;
+            }
+            write!(self.writer, "\r{}\x1b[K", line.format())?;
+            self.lines += 1;
+        }
+        write!(self.writer, "\x1b[J")?;
+        return self.writer.flush();
+    }
+
+    /// Add a bar, drawn by [MultiProgress::redraw], and
+    /// return a handle which can update it from any thread.
+    pub fn add(&mut self, bar : ProgressBar) -> ProgressHandle {
+        let handle = ProgressHandle {
+            bar : Arc::new(Mutex::new(bar))
+        };
+        self.bars.push(handle.clone());
+        return handle;
+    }
+
+    /// Draw every added bar over the lines drawn last time.
+    pub fn redraw(&mut self) -> io::Result<()> {
+        let lines = self.bars.iter().map(ProgressHandle::render).collect::<Vec<ColouredString>>();
+        return self.draw(lines);
+    }
+
+    /// Move below the drawn lines, so they are kept
+    /// and the next draw starts afresh.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if (self.lines > 0) {
+            writeln!(self.writer)?;
+        }
+        self.lines = 0;
+        return self.writer.flush();
+    }
+
+}
+
+
+/// A shared handle to a [ProgressBar] added to a
+/// [MultiProgress], which can be cloned and sent to
+/// other threads.
+#[derive(Debug, Clone)]
+pub struct ProgressHandle {
+    bar : Arc<Mutex<ProgressBar>>
+}
+
+/// Progress
+impl ProgressHandle {
+
+    /// Update the bar.
+    pub fn update<R, F : FnOnce(&mut ProgressBar) -> R>(&self, update : F) -> R {
+        return update(&mut self.bar.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+
+    /// Move the bar forward. See [ProgressBar::inc].
+    pub fn inc(&self, delta : u64) {
+        self.update(|bar| bar.inc(delta));
+    }
+
+    /// Set the message. See [ProgressBar::set_message].
+    pub fn set_message<S : Into<String>>(&self, message : S) {
+        self.update(|bar| bar.set_message(message));
+    }
+
+    /// Fill the bar. See [ProgressBar::finish].
+    pub fn finish(&self) {
+        self.update(ProgressBar::finish);
+    }
+
+}
+
+/// Rendering
+impl ProgressHandle {
+
+    /// Render the template of the bar.
+    pub fn render(&self) -> ColouredString {
+        return self.update(|bar| bar.render());
+    }
+
+}
@@ -0,0 +1,271 @@
+use std::{
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    }
+};
+
+use crate::{
+    builder::ColouredStringBuilder,
+    consts::Formatting,
+    descriptions::parse_colour,
+    diff::{
+        Diff,
+        DiffStyles
+    },
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// How [ColouredString::render] writes a `ColouredString`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderMode {
+    /// ANSI escape sequences, the same as [ColouredString::format].
+    Ansi,
+    /// The text without any formatting, the same as [ColouredString::unformat].
+    Plain,
+    /// Readable tags such as `<fg=red><bold>x</bold></fg>`.
+    /// See [ColouredString::parse_tags].
+    Tags
+}
+
+
+/// An error produced when tagged text can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagParseError {
+    /// The byte offset of the error.
+    pub offset  : usize,
+    /// A description of the error.
+    pub message : String
+}
+
+impl Display for TagParseError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}: {}", self.offset, self.message);
+    }
+}
+
+impl Error for TagParseError { }
+
+
+/// Returns the name and value of the tag for some formatting.
+fn tag(formatting : &Formatting) -> (&'static str, Option<String>) {
+    let colour = |formatting : &Formatting| match (formatting) {
+        Formatting::Fg8Bit(n)        | Formatting::Bg8Bit(n)        => n.to_string(),
+        Formatting::Fg24Bit(r, g, b) | Formatting::Bg24Bit(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Formatting::FgReset          | Formatting::BgReset          => String::from("default"),
+        other => {
+            let name = other.name().unwrap_or_default();
+            name.strip_prefix("bg_").unwrap_or(name).to_string()
+        }
+    };
+    return if (formatting.is_fg()) {
+        ("fg", Some(colour(formatting)))
+    } else if (formatting.is_bg()) {
+        ("bg", Some(colour(formatting)))
+    } else {
+        (formatting.name().unwrap_or_default(), None)
+    };
+}
+
+/// Returns the formatting of a style with the colours first.
+fn tag_order(style : &Style) -> Vec<Formatting> {
+    let mut formatting = style.formatting();
+    formatting.sort_by_key(|formatting| ! (formatting.is_fg() || formatting.is_bg()));
+    return formatting;
+}
+
+/// Escapes text for use between tags.
+fn escape(text : &str) -> String {
+    return text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+}
+
+
+/// Snapshots
+impl ColouredString {
+
+    /// Write this `ColouredString` in the given `RenderMode`.
+    ///
+    /// Tags describe the resolved style of the text rather than
+    /// how the `ColouredString` was built, so equal looking
+    /// strings always render the same. Colours come before other
+    /// formatting, and tags are kept open for as long as possible.
+    /// `&`, `<` and `>` are escaped as `&amp;`, `&lt;` and `&gt;`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{fg, bg, style, RenderMode};
+    /// let s = fg::red(style::bold("x") + "y") + " " + bg::bright_blue(fg::cbit_8("<z>", 196));
+    /// assert_eq!(s.render(RenderMode::Tags), "<fg=red><bold>x</bold>y</fg> <fg=196><bg=bright_blue>&lt;z&gt;</bg></fg>");
+    /// assert_eq!(s.render(RenderMode::Plain), "xy <z>");
+    /// assert_eq!(s.render(RenderMode::Ansi), s.format());
+    /// ```
+    pub fn render(&self, mode : RenderMode) -> String {
+        return match (mode) {
+            RenderMode::Ansi  => self.format(),
+            RenderMode::Plain => self.unformat(),
+            RenderMode::Tags  => self.to_tags()
+        };
+    }
+
+    /// See [ColouredString::render].
+    fn to_tags(&self) -> String {
+        let mut result = String::new();
+        let mut open   = Vec::<Formatting>::new();
+        for (text, style) in self.spans() {
+            let wanted = tag_order(&style);
+            let keep   = open.iter().position(|formatting| ! wanted.contains(formatting)).unwrap_or(open.len());
+            for formatting in open.drain(keep..).rev() {
+                result += &format!("</{}>", tag(&formatting).0);
+            }
+            for formatting in wanted {
+                if (! open.contains(&formatting)) {
+                    result += &match (tag(&formatting)) {
+                        (name, Some(value)) => format!("<{}={}>", name, value),
+                        (name, None)        => format!("<{}>", name)
+                    };
+                    open.push(formatting);
+                }
+            }
+            result += &escape(&text);
+        }
+        for formatting in open.into_iter().rev() {
+            result += &format!("</{}>", tag(&formatting).0);
+        }
+        return result;
+    }
+
+    /// Parse text written with [RenderMode::Tags].
+    ///
+    /// Tags are `<fg=colour>`, `<bg=colour>` and formatting names
+    /// such as `<bold>`, and are closed by `</fg>`, `</bg>` or
+    /// `</bold>`. Colours are written the same as in style
+    /// descriptions, such as `red`, `bright_red`, `196` or `#ff0000`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{ColouredString, Formatting, RenderMode};
+    /// let tags = "<fg=red><bold>x</bold>y</fg> a &lt; b";
+    /// let s = ColouredString::parse_tags(tags).unwrap();
+    /// assert_eq!(s.unformat(), "xy a < b");
+    /// assert!(s.style_at(0).is_bold());
+    /// assert_eq!(s.style_at(1).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(s.render(RenderMode::Tags), tags);
+    ///
+    /// assert_eq!(ColouredString::parse_tags("<bold>x</fg>").unwrap_err().offset, 7);
+    /// ```
+    pub fn parse_tags(text : &str) -> Result<ColouredString, TagParseError> {
+        let error = |offset : usize, message : String| TagParseError { offset, message };
+
+        let mut builder = ColouredStringBuilder::new();
+        let mut names   = Vec::<&str>::new();
+        let mut plain   = String::new();
+        let mut offset  = 0;
+        while (offset < text.len()) {
+            let rest = &text[offset..];
+            if (rest.starts_with('&')) {
+                let (ch, length) = if (rest.starts_with("&amp;")) {('&', 5)}
+                    else if (rest.starts_with("&lt;")) {('<', 4)}
+                    else if (rest.starts_with("&gt;")) {('>', 4)}
+                    else {return Err(error(offset, String::from("Invalid escape. Expected `&amp;`, `&lt;` or `&gt;`.")));};
+                plain.push(ch);
+                offset += length;
+                continue;
+            }
+            if (! rest.starts_with('<')) {
+                let ch = rest.chars().next().unwrap();
+                if (ch == '>') {
+                    return Err(error(offset, String::from("Unexpected `>`. Write `&gt;` instead.")));
+                }
+                plain.push(ch);
+                offset += ch.len_utf8();
+                continue;
+            }
+
+            let end  = rest.find('>').ok_or_else(|| error(offset, String::from("Unterminated tag.")))?;
+            let body = &rest[1..end];
+            if (! plain.is_empty()) {
+                builder.text(std::mem::take(&mut plain));
+            }
+            if let Some(name) = body.strip_prefix('/') {
+                match (names.pop()) {
+                    Some(open) if open == name => {
+                        builder.pop_style();
+                    },
+                    Some(open) => return Err(error(offset, format!("Expected `</{}>`, found `</{}>`.", open, name))),
+                    None       => return Err(error(offset, format!("Unexpected `</{}>`.", name)))
+                }
+            } else {
+                let formatting = match (body.split_once('=')) {
+                    Some((name @ ("fg" | "bg"), value)) => parse_colour(value, name == "bg")
+                        .map_err(|message| error(offset, message))?
+                        .ok_or_else(|| error(offset, format!("Unknown colour `{}`.", value)))?,
+                    _ => Formatting::from_name(body)
+                        .filter(|formatting| ! (formatting.is_fg() || formatting.is_bg() || formatting.is_reset()))
+                        .ok_or_else(|| error(offset, format!("Unknown tag `<{}>`.", body)))?
+                };
+                names.push(body.split('=').next().unwrap_or_default());
+                builder.push_style(formatting);
+            }
+            offset += end + 1;
+        }
+        if let Some(open) = names.last() {
+            return Err(error(text.len(), format!("Expected `</{}>`.", open)));
+        }
+        if (! plain.is_empty()) {
+            builder.text(plain);
+        }
+        return Ok(builder.build());
+    }
+
+}
+
+
+/// Compares two snapshots line by line. Returns `None` if they
+/// are equal, or a [Diff] with removed lines of `expected` in
+/// red and added lines of `actual` in green.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{fg, snapshot_diff, RenderMode};
+/// let actual = fg::red("error").render(RenderMode::Tags);
+/// assert!(snapshot_diff("<fg=red>error</fg>", &actual).is_none());
+///
+/// let diff = snapshot_diff("a\n<fg=green>error</fg>", &format!("a\n{}", actual)).unwrap();
+/// assert_eq!(diff.unformat(), "  a\n- <fg=green>error</fg>\n+ <fg=red>error</fg>\n");
+/// ```
+pub fn snapshot_diff(expected : &str, actual : &str) -> Option<ColouredString> {
+    if (expected == actual) {
+        return None;
+    }
+    return Some(Diff::new(expected, actual).inline(&DiffStyles::default()));
+}
+
+/// Asserts that the [RenderMode::Tags] form of a `ColouredString`
+/// equals a snapshot, panicking with a diff if it does not.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{assert_snapshot, fg, style};
+/// assert_snapshot!(fg::red("a") + style::bold("b"), "<fg=red>a</fg><bold>b</bold>");
+/// ```
+///
+/// ```should_panic
+/// use vibrance::{assert_snapshot, fg};
+/// assert_snapshot!(fg::red("a"), "<fg=green>a</fg>");
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($actual:expr, $expected:expr $(,)?) => {
+        if let Some(diff) = $crate::snapshot_diff(&$expected, &($actual).render($crate::RenderMode::Tags)) {
+            panic!("Snapshots differ:\n{}", diff);
+        }
+    };
+}
@@ -10,9 +10,14 @@ use std::{
     }
 };
 
-use crate::consts::{
-    Formatting,
-    FORMAT_RESET
+use crate::{
+    traits::Colourisable,
+    consts::{
+        Formatting,
+        FORMAT_RESET
+    },
+    styles::Style,
+    theme::Theme
 };
 
 
@@ -44,7 +49,8 @@ use crate::consts::{
 #[derive(Debug, Clone)]
 pub struct ColouredString {
     pub(crate) parts      : Vec<ColouredStringPart>,
-    pub(crate) formatting : Vec<Formatting>
+    pub(crate) formatting : Vec<Formatting>,
+    pub(crate) semantic   : Option<String>
 }
 
 /// Initialisation
@@ -61,7 +67,8 @@ impl ColouredString {
     pub fn new() -> ColouredString {
         return ColouredString {
             parts      : Vec::new(),
-            formatting : Vec::new()
+            formatting : Vec::new(),
+            semantic   : None
         };
     }
     /// Create a new unformatted `ColouredString` containing some text.
@@ -86,7 +93,8 @@ impl ColouredString {
     pub(crate) fn from_part(part : ColouredStringPart) -> ColouredString {
         return ColouredString {
             parts      : vec![part],
-            formatting : Vec::new()
+            formatting : Vec::new(),
+            semantic   : None
         };
     }
     /// Create a new `ColouredString` containing some text and formatting.
@@ -95,11 +103,42 @@ impl ColouredString {
     pub fn from_formatting<S : Into<String>>(text : S, formatting : Vec<Formatting>) -> ColouredString {
         return ColouredString {
             parts      : vec![ColouredStringPart::String(text.into())],
-            formatting : formatting
+            formatting,
+            semantic   : None
+        };
+    }
+    /// Create a new `ColouredString` tagged with a semantic name.
+    /// The style of the name is looked up in the active
+    /// `Theme` whenever the string is formatted.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Any `Colourisable` to tag.
+    /// * `name` - The semantic name, such as `error` or `error.code`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{ColouredString, Formatting};
+    /// let s = ColouredString::from_semantic("failed", "error");
+    /// assert_eq!(s.semantic(), Some("error"));
+    /// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgRed));
+    /// ```
+    pub fn from_semantic<S : Colourisable, T : Into<String>>(text : S, name : T) -> ColouredString {
+        return ColouredString {
+            parts      : vec![ColouredStringPart::Sub(Box::new(text.formatted(Vec::new())))],
+            formatting : Vec::new(),
+            semantic   : Some(name.into())
         };
     }
 }
 
+impl Default for ColouredString {
+    fn default() -> ColouredString {
+        return ColouredString::new();
+    }
+}
+
 /// Mutation and Getters
 impl ColouredString {
 
@@ -130,11 +169,30 @@ impl ColouredString {
     }
 
 
+    /// Returns the semantic name of this `ColouredString`, if it has one.
+    /// See [ColouredString::from_semantic].
+    pub fn semantic(&self) -> Option<&str> {
+        return self.semantic.as_deref();
+    }
+
+    /// Returns the formatting of this `ColouredString`,
+    /// followed by the style of its semantic name in the
+    /// active `Theme`.
+    /// 
+    /// # Internal
+    fn node_formatting(&self) -> Vec<Formatting> {
+        let mut formatting = self.formatting.clone();
+        if let Some(name) = &self.semantic {
+            formatting.append(&mut Theme::resolve_active(name).formatting());
+        }
+        return formatting;
+    }
+
     /// Format the parts with the formatting and given prefix.
-    fn format_next(&self, prefix : &Vec<String>) -> String {
+    fn format_next(&self, prefix : &[String]) -> String {
         let mut result = String::new();
-        let mut next_prefix = prefix.clone();
-        next_prefix.append(&mut self.formatting.iter().map(|f| format!("{}", f)).collect::<Vec<String>>());
+        let mut next_prefix = prefix.to_vec();
+        next_prefix.append(&mut self.node_formatting().iter().map(|f| format!("{}", f)).collect::<Vec<String>>());
         for part in &self.parts {
             result += part.format_next(&next_prefix).as_str();
         }
@@ -207,9 +265,11 @@ impl ColouredString {
         let mut left_string = ColouredString::new();
         left_string.parts      = left;
         left_string.formatting = self.formatting.clone();
+        left_string.semantic   = self.semantic.clone();
         let mut right_string = ColouredString::new();
         right_string.parts      = right;
         right_string.formatting = self.formatting.clone();
+        right_string.semantic   = self.semantic.clone();
         return [left_string, right_string];
     }
 
@@ -339,6 +399,56 @@ impl ColouredString {
         return center;
     }
 
+    /// Applies formatting to the text within the given range.
+    /// The formatting is applied on top of any formatting
+    /// the text already has.
+    /// 
+    /// # Arguments
+    /// 
+    /// * range : A `RangeBounds<usize>` which specifies the start and end of the range to format.
+    /// * formatting : A `Vec<Formatting>` to apply to the range.
+    /// 
+    /// # Panics
+    /// 
+    /// * The end of the range is greater than the length of this `ColouredString`.
+    /// * The start of the range is greater than the end.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let mut f = fg::red("redgreen") + "none";
+    /// f.format_range(3..10, vec![Formatting::FgGreen]);
+    /// assert_eq!(f.unformat(), "redgreennone");
+    /// assert_eq!(f.style_at(2).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(f.style_at(3).fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(f.style_at(9).fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(f.style_at(10).fg(), None);
+    /// ```
+    pub fn format_range<R : RangeBounds<usize>>(&mut self, range : R, formatting : Vec<Formatting>) {
+        let [left, mut center, right] = self.split_3(range);
+        center.format_leaves(&formatting);
+        let mut result = ColouredString::new();
+        result.parts.push(ColouredStringPart::Sub(Box::new(left)));
+        result.parts.push(ColouredStringPart::Sub(Box::new(center)));
+        result.parts.push(ColouredStringPart::Sub(Box::new(right)));
+        *self = result;
+    }
+
+    /// Wraps every text part in the given formatting,
+    /// so that it overrides any formatting further out.
+    /// 
+    /// # Internal
+    fn format_leaves(&mut self, formatting : &[Formatting]) {
+        for part in &mut self.parts {
+            if let ColouredStringPart::String(string) = part {
+                *part = ColouredStringPart::Sub(Box::new(ColouredString::from_formatting(string.as_str(), formatting.to_vec())));
+            } else if let ColouredStringPart::Sub(string) = part {
+                string.format_leaves(formatting);
+            }
+        }
+    }
+
     /// Shortens this `ColouredString` to the specified length.
     /// 
     /// # Arguments
@@ -448,6 +558,509 @@ impl ColouredString {
 
 }
 
+/// Text Operations
+impl ColouredString {
+
+    /// Returns a `ColouredString` of the text between
+    /// the given byte indices with formatting.
+    /// 
+    /// # Internal
+    fn slice(&self, start : usize, end : usize) -> ColouredString {
+        let [_, center, _] = self.split_3(start..end);
+        return center;
+    }
+
+    /// Returns the formatting which applies to the
+    /// character at the given byte index, outermost first.
+    /// 
+    /// # Internal
+    pub(crate) fn formatting_at(&self, mut idx : usize) -> Vec<Formatting> {
+        let mut formatting = self.node_formatting();
+        for part in &self.parts {
+            if (idx < part.len()) {
+                if let ColouredStringPart::Sub(string) = part {
+                    formatting.append(&mut string.formatting_at(idx));
+                }
+                return formatting;
+            }
+            idx -= part.len();
+        }
+        return formatting;
+    }
+
+    /// Returns this `ColouredString` with leading and
+    /// trailing whitespace removed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let t = fg::red("  red ") + fg::blue("blue  ");
+    /// assert_eq!(t.trim().unformat(), "red blue");
+    /// assert_eq!(t.trim().to_string(), "\x1b[31mred \x1b[0m\x1b[34mblue\x1b[0m");
+    /// ```
+    pub fn trim(&self) -> ColouredString {
+        let text  = self.unformat();
+        let start = text.len() - text.trim_start().len();
+        return self.slice(start, start + text.trim().len());
+    }
+
+    /// Returns this `ColouredString` with leading
+    /// whitespace removed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let t = fg::red("  red ");
+    /// assert_eq!(t.trim_start().unformat(), "red ");
+    /// assert_eq!(t.trim_start().to_string(), "\x1b[31mred \x1b[0m");
+    /// ```
+    pub fn trim_start(&self) -> ColouredString {
+        let text = self.unformat();
+        return self.slice(text.len() - text.trim_start().len(), text.len());
+    }
+
+    /// Returns this `ColouredString` with trailing
+    /// whitespace removed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let t = fg::red("  red ");
+    /// assert_eq!(t.trim_end().unformat(), "  red");
+    /// assert_eq!(t.trim_end().style_at(4).fg(), Some(Formatting::FgRed));
+    /// ```
+    pub fn trim_end(&self) -> ColouredString {
+        return self.slice(0, self.unformat().trim_end().len());
+    }
+
+    /// Splits this `ColouredString` by the given pattern.
+    /// Behaves like [str::split].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pattern` - The text to split at.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let s = fg::red("a,b") + ",c";
+    /// let parts = s.split(",");
+    /// assert_eq!(parts.iter().map(|part| part.unformat()).collect::<Vec<String>>(), ["a", "b", "c"]);
+    /// assert_eq!(parts[1].style_at(0).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(parts[2].style_at(0).fg(), None);
+    /// ```
+    pub fn split(&self, pattern : &str) -> Vec<ColouredString> {
+        let     text   = self.unformat();
+        let mut result = Vec::new();
+        let mut start  = 0;
+        for (idx, matched) in text.match_indices(pattern) {
+            result.push(self.slice(start, idx));
+            start = idx + matched.len();
+        }
+        result.push(self.slice(start, text.len()));
+        return result;
+    }
+
+    /// Splits this `ColouredString` by whitespace.
+    /// Behaves like [str::split_whitespace].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red(" foo  bar") + fg::blue("baz\n");
+    /// let parts = s.split_whitespace().iter().map(|part| part.unformat()).collect::<Vec<String>>();
+    /// assert_eq!(parts, ["foo", "barbaz"]);
+    /// ```
+    pub fn split_whitespace(&self) -> Vec<ColouredString> {
+        let     text   = self.unformat();
+        let mut result = Vec::new();
+        let mut start  = None;
+        for (idx, ch) in text.char_indices() {
+            match (start, ch.is_whitespace()) {
+                (Some(word_start), true) => {
+                    result.push(self.slice(word_start, idx));
+                    start = None;
+                },
+                (None, false) => start = Some(idx),
+                _             => { }
+            }
+        }
+        if let Some(word_start) = start {
+            result.push(self.slice(word_start, text.len()));
+        }
+        return result;
+    }
+
+    /// Splits this `ColouredString` into lines.
+    /// Behaves like [str::lines].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let s = fg::red("foo\r\nbar") + "\nbaz\n";
+    /// let lines = s.lines();
+    /// assert_eq!(lines.iter().map(|line| line.unformat()).collect::<Vec<String>>(), ["foo", "bar", "baz"]);
+    /// assert_eq!(lines[1].style_at(2).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(lines[2].style_at(0).fg(), None);
+    /// ```
+    pub fn lines(&self) -> Vec<ColouredString> {
+        let     text   = self.unformat();
+        let mut result = Vec::new();
+        let mut start  = 0;
+        while (start < text.len()) {
+            let end  = text[start..].find('\n').map_or(text.len(), |idx| start + idx);
+            let line = &text[start..end];
+            result.push(self.slice(start, start + line.strip_suffix('\r').unwrap_or(line).len()));
+            start = end + 1;
+        }
+        return result;
+    }
+
+    /// Returns the byte index of the first match of
+    /// the given pattern, if any.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("foo") + "bar" + fg::blue("foo");
+    /// assert_eq!(s.find("foo"), Some(0));
+    /// assert_eq!(s.find("baz"), None);
+    /// ```
+    pub fn find(&self, pattern : &str) -> Option<usize> {
+        return self.unformat().find(pattern);
+    }
+
+    /// Returns the byte index of the last match of
+    /// the given pattern, if any.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("foo") + "bar" + fg::blue("foo");
+    /// assert_eq!(s.rfind("foo"), Some(6));
+    /// ```
+    pub fn rfind(&self, pattern : &str) -> Option<usize> {
+        return self.unformat().rfind(pattern);
+    }
+
+    /// Returns `true` if the given pattern matches
+    /// somewhere in this `ColouredString`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("foo") + "bar";
+    /// assert!(s.contains("ob"));
+    /// assert!(! s.contains("baz"));
+    /// ```
+    pub fn contains(&self, pattern : &str) -> bool {
+        return self.unformat().contains(pattern);
+    }
+
+    /// Returns `true` if this `ColouredString` starts
+    /// with the given pattern.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("foo") + "bar";
+    /// assert!(s.starts_with("foob"));
+    /// ```
+    pub fn starts_with(&self, pattern : &str) -> bool {
+        return self.unformat().starts_with(pattern);
+    }
+
+    /// Returns `true` if this `ColouredString` ends
+    /// with the given pattern.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("foo") + "bar";
+    /// assert!(s.ends_with("obar"));
+    /// ```
+    pub fn ends_with(&self, pattern : &str) -> bool {
+        return self.unformat().ends_with(pattern);
+    }
+
+    /// Replaces all matches of a pattern with another string.
+    /// The replacement inherits the formatting of the
+    /// position it was inserted at.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `from` - The text to replace.
+    /// * `to` - The text to replace it with.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let s = fg::red("foo bar") + " foo";
+    /// let r = s.replace("foo", fg::blue("baz"));
+    /// assert_eq!(r.unformat(), "baz bar baz");
+    /// assert_eq!(r.style_at(0).fg(), Some(Formatting::FgBlue));
+    /// assert_eq!(r.style_at(4).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(r.style_at(8).fg(), Some(Formatting::FgBlue));
+    /// ```
+    pub fn replace<S : Into<ColouredString>>(&self, from : &str, to : S) -> ColouredString {
+        return self.replacen(from, to, usize::MAX);
+    }
+
+    /// Replaces the first `count` matches of a pattern
+    /// with another string.
+    /// The replacement inherits the formatting of the
+    /// position it was inserted at.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `from` - The text to replace.
+    /// * `to` - The text to replace it with.
+    /// * `count` - The maximum number of matches to replace.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let s = fg::red("foo bar") + " foo";
+    /// let r = s.replacen("foo", "baz", 1);
+    /// assert_eq!(r.unformat(), "baz bar foo");
+    /// assert_eq!(r.style_at(0).fg(), Some(Formatting::FgRed));
+    /// assert_eq!(r.style_at(8).fg(), None);
+    /// ```
+    pub fn replacen<S : Into<ColouredString>>(&self, from : &str, to : S, count : usize) -> ColouredString {
+        let     text   = self.unformat();
+        let     to     = to.into();
+        let mut result = ColouredString::new();
+        let mut start  = 0;
+        for (idx, matched) in text.match_indices(from).take(count) {
+            result.parts.push(ColouredStringPart::Sub(Box::new(self.slice(start, idx))));
+            result.parts.push(ColouredStringPart::Sub(Box::new(to.clone().formatted(self.formatting_at(idx)))));
+            start = idx + matched.len();
+        }
+        result.parts.push(ColouredStringPart::Sub(Box::new(self.slice(start, text.len()))));
+        return result;
+    }
+
+    /// Returns this `ColouredString` with every
+    /// character converted to uppercase.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("foo") + "bar";
+    /// assert_eq!(s.to_uppercase().unformat(), "FOOBAR");
+    /// ```
+    pub fn to_uppercase(&self) -> ColouredString {
+        let mut result = self.clone();
+        result.map_text(|string| string.to_uppercase());
+        return result;
+    }
+
+    /// Returns this `ColouredString` with every
+    /// character converted to lowercase.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("FOO") + "BAR";
+    /// assert_eq!(s.to_lowercase().unformat(), "foobar");
+    /// ```
+    pub fn to_lowercase(&self) -> ColouredString {
+        let mut result = self.clone();
+        result.map_text(|string| string.to_lowercase());
+        return result;
+    }
+
+}
+
+/// Restyling
+impl ColouredString {
+
+    /// Replaces every `Formatting` in this `ColouredString`
+    /// with the formatting returned by the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `function` - A function which returns the formatting to replace each `Formatting` with.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, style, Formatting};
+    /// let mut s = fg::red("foo") + style::bold("bar");
+    /// s.map_styles(|formatting| match (formatting) {
+    ///     Formatting::FgRed => vec![Formatting::Fg24Bit(255, 128, 0)],
+    ///     Formatting::Bold  => vec![Formatting::Bold, Formatting::Underline],
+    ///     other             => vec![*other]
+    /// });
+    /// assert_eq!(s.style_at(0).fg(), Some(Formatting::Fg24Bit(255, 128, 0)));
+    /// assert!(s.style_at(3).is_underline());
+    /// ```
+    pub fn map_styles<F : FnMut(&Formatting) -> Vec<Formatting>>(&mut self, mut function : F) {
+        self.map_styles_next(&mut function);
+    }
+
+    /// See [ColouredString::map_styles].
+    fn map_styles_next<F : FnMut(&Formatting) -> Vec<Formatting>>(&mut self, function : &mut F) {
+        self.formatting = self.formatting.iter().flat_map(&mut *function).collect();
+        for part in &mut self.parts {
+            if let ColouredStringPart::Sub(string) = part {
+                string.map_styles_next(function);
+            }
+        }
+    }
+
+    /// Removes every `Formatting` in this `ColouredString`
+    /// which matches the given predicate.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `predicate` - A function which returns `true` for each `Formatting` to remove.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, style, Formatting};
+    /// let mut s = style::slow_blink(fg::red("foo"));
+    /// s.remove_formatting(|formatting| *formatting == Formatting::SlowBlink);
+    /// assert!(! s.style_at(0).is_slow_blink());
+    /// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgRed));
+    /// ```
+    pub fn remove_formatting<F : FnMut(&Formatting) -> bool>(&mut self, mut predicate : F) {
+        self.map_styles_next(&mut |formatting : &Formatting| if (predicate(formatting)) {
+            Vec::new()
+        } else {
+            vec![*formatting]
+        });
+    }
+
+    /// Replaces the text of every part of this `ColouredString`
+    /// with the text returned by the given function.
+    /// Formatting is left unchanged.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `function` - A function which returns the text to replace each part with.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::fg;
+    /// let mut s = fg::red("foo") + "bar";
+    /// s.map_text(|text| text.chars().rev().collect());
+    /// assert_eq!(s.unformat(), "oofrab");
+    /// ```
+    pub fn map_text<F : FnMut(&str) -> String>(&mut self, mut function : F) {
+        self.map_text_next(&mut function);
+    }
+
+    /// See [ColouredString::map_text].
+    fn map_text_next<F : FnMut(&str) -> String>(&mut self, function : &mut F) {
+        for part in &mut self.parts {
+            match (part) {
+                ColouredStringPart::String (string) => *string = function(string),
+                ColouredStringPart::Sub    (string) => string.map_text_next(function)
+            }
+        }
+    }
+
+}
+
+/// Style Queries
+impl ColouredString {
+
+    /// Returns the fully resolved `Style` of the character
+    /// at the given byte index. Formatting is accumulated
+    /// the same way it is when formatting, so more deeply
+    /// nested formatting overrides formatting of the same
+    /// category further out.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `index` - The byte index of the character.
+    /// 
+    /// # Panics
+    /// 
+    /// * The index is greater than or equal to the length of this `ColouredString`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, style, Formatting};
+    /// let s = "error " + style::bold(fg::red("E0001") + ": failed");
+    /// assert_eq!(s.style_at(0).fg(), None);
+    /// assert_eq!(s.style_at(6).fg(), Some(Formatting::FgRed));
+    /// assert!(s.style_at(6).is_bold());
+    /// assert_eq!(s.style_at(11).fg(), None);
+    /// ```
+    pub fn style_at(&self, index : usize) -> Style {
+        assert!(index < self.len(), "Byte index out of bounds.");
+        return Style::from(self.formatting_at(index)).resolved();
+    }
+
+    /// Returns every character of this `ColouredString`
+    /// along with its fully resolved `Style`.
+    /// See [ColouredString::style_at].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting};
+    /// let s = "a" + fg::red("b");
+    /// let chars = s.styled_chars();
+    /// assert_eq!(chars[0].0, 'a');
+    /// assert!(chars[0].1.is_plain());
+    /// assert_eq!(chars[1].0, 'b');
+    /// assert_eq!(chars[1].1.fg(), Some(Formatting::FgRed));
+    /// ```
+    pub fn styled_chars(&self) -> Vec<(char, Style)> {
+        return self.spans().into_iter()
+            .flat_map(|(text, style)| text.chars().map(|ch| (ch, style)).collect::<Vec<(char, Style)>>())
+            .collect();
+    }
+
+    /// Returns the text of every non-empty part of this
+    /// `ColouredString` along with its fully resolved `Style`.
+    /// 
+    /// # Internal
+    pub(crate) fn spans(&self) -> Vec<(String, Style)> {
+        let mut result = Vec::new();
+        self.spans_next(&Style::new(), &mut result);
+        return result;
+    }
+
+    /// See [ColouredString::spans].
+    fn spans_next(&self, prefix : &Style, result : &mut Vec<(String, Style)>) {
+        let mut style = *prefix;
+        for formatting in self.node_formatting() {
+            style.apply(formatting);
+        }
+        for part in &self.parts {
+            match (part) {
+                ColouredStringPart::String(string) => if (! string.is_empty()) {
+                    result.push((string.clone(), style.resolved()));
+                },
+                ColouredStringPart::Sub(string) => string.spans_next(&style, result)
+            }
+        }
+    }
+
+}
+
 /// Formatting
 impl Display for ColouredString {
     fn fmt(&self, f : &mut Formatter<'_>) -> Result {
@@ -477,7 +1090,7 @@ impl ColouredStringPart {
     }
 
     /// See [ColouredString::format].
-    fn format_next(&self, prefix : &Vec<String>) -> String {
+    fn format_next(&self, prefix : &[String]) -> String {
         return match (self) {
             ColouredStringPart::String(string) => format!(
                 "{}{}{}",
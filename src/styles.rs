@@ -0,0 +1,279 @@
+use crate::{
+    traits::Colourisable,
+    consts::Formatting,
+    strings::ColouredString
+};
+
+
+/// The fully resolved formatting of some text.
+/// 
+/// Each category of formatting (intensity, italic,
+/// foreground colour, etc) holds at most one value.
+/// Applying a `Formatting` replaces the previous value
+/// of the same category, the same way a terminal would.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{fg, style, Formatting, Style};
+/// let s = fg::red("foo" + fg::blue(style::bold("bar")));
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgRed));
+/// assert_eq!(s.style_at(3).fg(), Some(Formatting::FgBlue));
+/// assert!(s.style_at(3).is_bold());
+/// 
+/// let t = Style::from(vec![Formatting::Bold, Formatting::FgRed]);
+/// assert_eq!(t.paint("baz").style_at(0), t);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    intensity     : Option<Formatting>,
+    italic        : Option<Formatting>,
+    underline     : Option<Formatting>,
+    slow_blink    : Option<Formatting>,
+    fast_blink    : Option<Formatting>,
+    invert        : Option<Formatting>,
+    conceal       : Option<Formatting>,
+    strikethrough : Option<Formatting>,
+    overline      : Option<Formatting>,
+    fg            : Option<Formatting>,
+    bg            : Option<Formatting>
+}
+
+/// Initialisation
+impl Style {
+    /// Create a new `Style` with no formatting.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::Style;
+    /// assert!(Style::new().is_plain());
+    /// ```
+    pub fn new() -> Style {
+        return Style::default();
+    }
+
+    /// Returns this `Style` with the given formatting applied.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Style};
+    /// let s = Style::new().with(Formatting::FgRed).with(Formatting::FgGreen);
+    /// assert_eq!(s.fg(), Some(Formatting::FgGreen));
+    /// ```
+    pub fn with(mut self, formatting : Formatting) -> Style {
+        self.apply(formatting);
+        return self;
+    }
+}
+
+/// Mutation and Getters
+impl Style {
+
+    /// Returns the slot which holds the category of the given formatting.
+    /// `None` is returned for `Formatting::None`, which affects every category.
+    /// 
+    /// # Internal
+    fn slot(&mut self, formatting : &Formatting) -> Option<&mut Option<Formatting>> {
+        return Some(match (formatting) {
+            Formatting::None => return None,
+
+            Formatting::Bold
+            | Formatting::Faint
+            | Formatting::ResetIntensity     => &mut self.intensity,
+            Formatting::Italic
+            | Formatting::ResetItalic        => &mut self.italic,
+            Formatting::Underline
+            | Formatting::ResetUnderline     => &mut self.underline,
+            Formatting::SlowBlink
+            | Formatting::ResetSlowBlink     => &mut self.slow_blink,
+            Formatting::FastBlink
+            | Formatting::ResetFastBlink     => &mut self.fast_blink,
+            Formatting::Invert
+            | Formatting::ResetInvert        => &mut self.invert,
+            Formatting::Conceal
+            | Formatting::ResetConceal       => &mut self.conceal,
+            Formatting::Strikethrough
+            | Formatting::ResetStrikethrough => &mut self.strikethrough,
+            Formatting::Overline
+            | Formatting::ResetOverline      => &mut self.overline,
+
+            fg if fg.is_fg() => &mut self.fg,
+            bg if bg.is_bg() => &mut self.bg,
+            _                => unreachable!("Every formatting variant has a category.")
+        });
+    }
+
+    /// Returns every slot of this `Style`, in the order they are emitted.
+    /// 
+    /// # Internal
+    fn slots(&self) -> [Option<Formatting>; 11] {
+        return [
+            self.intensity,
+            self.italic,
+            self.underline,
+            self.slow_blink,
+            self.fast_blink,
+            self.invert,
+            self.conceal,
+            self.strikethrough,
+            self.overline,
+            self.fg,
+            self.bg
+        ];
+    }
+
+    /// Apply some formatting to this `Style`, replacing
+    /// any previous formatting of the same category.
+    /// `Formatting::None` clears every category.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `formatting` - The `Formatting` to apply.
+    pub fn apply(&mut self, formatting : Formatting) {
+        match (self.slot(&formatting)) {
+            Some(slot) => *slot = Some(formatting),
+            None       => *self = Style::new()
+        }
+    }
+
+    /// Returns a new `Style` with the formatting of
+    /// `other` applied on top of this one.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Style};
+    /// let a = Style::from(vec![Formatting::Bold, Formatting::FgRed]);
+    /// let b = Style::from(Formatting::FgBlue);
+    /// assert_eq!(a.merge(&b), Style::from(vec![Formatting::Bold, Formatting::FgBlue]));
+    /// ```
+    pub fn merge(&self, other : &Style) -> Style {
+        let mut result = *self;
+        for formatting in other.formatting() {
+            result.apply(formatting);
+        }
+        return result;
+    }
+
+    /// Returns this `Style` with every reset variant removed,
+    /// as they are equivalent to no formatting once resolved.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Style};
+    /// let s = Style::from(vec![Formatting::Italic, Formatting::ResetItalic]);
+    /// assert!(s.resolved().is_plain());
+    /// ```
+    pub fn resolved(&self) -> Style {
+        let mut result = Style::new();
+        for formatting in self.formatting() {
+            if (! formatting.is_reset()) {
+                result.apply(formatting);
+            }
+        }
+        return result;
+    }
+
+    /// Returns the formatting of this `Style`, one entry per set category.
+    pub fn formatting(&self) -> Vec<Formatting> {
+        return self.slots().into_iter().flatten().collect();
+    }
+
+    /// Returns `true` if no category of this `Style` is set.
+    pub fn is_plain(&self) -> bool {
+        return self.slots().iter().all(|slot| slot.is_none());
+    }
+
+    /// Returns the foreground colour, if set.
+    pub fn fg(&self) -> Option<Formatting> {
+        return self.fg;
+    }
+
+    /// Returns the background colour, if set.
+    pub fn bg(&self) -> Option<Formatting> {
+        return self.bg;
+    }
+
+    /// Returns `true` if the text is bold.
+    pub fn is_bold(&self) -> bool {
+        return self.intensity == Some(Formatting::Bold);
+    }
+
+    /// Returns `true` if the text is faint.
+    pub fn is_faint(&self) -> bool {
+        return self.intensity == Some(Formatting::Faint);
+    }
+
+    /// Returns `true` if the text is italic.
+    pub fn is_italic(&self) -> bool {
+        return self.italic == Some(Formatting::Italic);
+    }
+
+    /// Returns `true` if the text is underlined.
+    pub fn is_underline(&self) -> bool {
+        return self.underline == Some(Formatting::Underline);
+    }
+
+    /// Returns `true` if the text blinks slowly.
+    pub fn is_slow_blink(&self) -> bool {
+        return self.slow_blink == Some(Formatting::SlowBlink);
+    }
+
+    /// Returns `true` if the text blinks quickly.
+    pub fn is_fast_blink(&self) -> bool {
+        return self.fast_blink == Some(Formatting::FastBlink);
+    }
+
+    /// Returns `true` if the foreground and background colours are swapped.
+    pub fn is_invert(&self) -> bool {
+        return self.invert == Some(Formatting::Invert);
+    }
+
+    /// Returns `true` if the text is hidden.
+    pub fn is_conceal(&self) -> bool {
+        return self.conceal == Some(Formatting::Conceal);
+    }
+
+    /// Returns `true` if the text is struck through.
+    pub fn is_strikethrough(&self) -> bool {
+        return self.strikethrough == Some(Formatting::Strikethrough);
+    }
+
+    /// Returns `true` if the text is overlined.
+    pub fn is_overline(&self) -> bool {
+        return self.overline == Some(Formatting::Overline);
+    }
+
+    /// Create a new `ColouredString` with this `Style` applied.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Any `Colourisable` to format.
+    pub fn paint<S : Colourisable>(&self, text : S) -> ColouredString {
+        return text.formatted(self.formatting());
+    }
+
+}
+
+impl From<Formatting> for Style {
+    fn from(formatting : Formatting) -> Self {
+        return Style::new().with(formatting);
+    }
+}
+impl From<Vec<Formatting>> for Style {
+    fn from(formatting : Vec<Formatting>) -> Self {
+        let mut style = Style::new();
+        for f in formatting {
+            style.apply(f);
+        }
+        return style;
+    }
+}
+impl From<Style> for Vec<Formatting> {
+    fn from(style : Style) -> Self {
+        return style.formatting();
+    }
+}
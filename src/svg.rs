@@ -0,0 +1,250 @@
+use std::fmt::Write;
+
+use crate::{
+    palette::{
+        Palette,
+        Rgb
+    },
+    strings::ColouredString,
+    styles::Style,
+    width::char_width
+};
+
+
+/// The colours of the buttons of the window frame.
+const FRAME_BUTTONS : [Rgb; 3] = [
+    Rgb::from_u32(0xff5f56),
+    Rgb::from_u32(0xffbd2e),
+    Rgb::from_u32(0x27c93f)
+];
+
+
+/// Options for [ColouredString::to_svg].
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{Palette, SvgOptions};
+/// let options = SvgOptions {
+///     palette : Palette::dracula(),
+///     title   : Some(String::from("cargo build")),
+///     ..SvgOptions::default()
+/// };
+/// assert!(options.frame);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// The palette used to resolve colours.
+    pub palette     : Palette,
+    /// The CSS font family of the text.
+    pub font_family : String,
+    /// The font size, in pixels.
+    pub font_size   : f64,
+    /// The width of a cell, as a multiple of the font size.
+    pub cell_width  : f64,
+    /// The height of a line, as a multiple of the font size.
+    pub line_height : f64,
+    /// The space around the text, in pixels.
+    pub padding     : f64,
+    /// The number of columns. If `None`, the
+    /// length of the longest line is used.
+    pub columns     : Option<usize>,
+    /// Whether to draw a window frame.
+    pub frame       : bool,
+    /// The title of the window frame.
+    pub title       : Option<String>
+}
+
+/// Initialisation
+impl SvgOptions {
+
+    /// Create new `SvgOptions` with the default values.
+    pub fn new() -> SvgOptions {
+        return SvgOptions::default();
+    }
+
+}
+
+impl Default for SvgOptions {
+    fn default() -> SvgOptions {
+        return SvgOptions {
+            palette     : Palette::default(),
+            font_family : String::from("ui-monospace, 'DejaVu Sans Mono', Menlo, Consolas, monospace"),
+            font_size   : 14.0,
+            cell_width  : 0.6,
+            line_height : 1.4,
+            padding     : 12.0,
+            columns     : None,
+            frame       : true,
+            title       : None
+        };
+    }
+}
+
+
+/// Formats a number with at most 2 decimal places.
+fn number(value : f64) -> String {
+    let text = format!("{:.2}", value);
+    return text.trim_end_matches('0').trim_end_matches('.').to_string();
+}
+
+/// Escapes text for use in XML.
+fn escape(text : &str) -> String {
+    let mut result = String::new();
+    for ch in text.chars() {
+        match (ch) {
+            '&'  => result.push_str("&amp;"),
+            '<'  => result.push_str("&lt;"),
+            '>'  => result.push_str("&gt;"),
+            '"'  => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _    => result.push(ch)
+        }
+    }
+    return result;
+}
+
+/// Splits a `ColouredString` into lines of styled characters,
+/// expanding tabs to the next multiple of 8 columns and
+/// dropping characters which end past `limit` columns.
+fn cells(string : &ColouredString, limit : usize) -> Vec<Vec<(char, Style)>> {
+    let mut lines  = vec![Vec::new()];
+    let mut column = 0;
+    for (ch, style) in string.styled_chars() {
+        let line = lines.last_mut().unwrap();
+        match (ch) {
+            '\n' => {
+                lines.push(Vec::new());
+                column = 0;
+            },
+            '\t' => {
+                let spaces = (8 - column % 8).min(limit.saturating_sub(column));
+                line.extend(std::iter::repeat_n((' ', style), spaces));
+                column += spaces;
+            },
+            ch if ch.is_control() => { },
+            _    => if (column + char_width(ch) <= limit) {
+                line.push((ch, style));
+                column += char_width(ch);
+            }
+        }
+    }
+    if (lines.len() > 1 && lines.last().is_some_and(|line| line.is_empty())) {
+        lines.pop();
+    }
+    return lines;
+}
+
+
+/// Exporting
+impl ColouredString {
+
+    /// Render this `ColouredString` as an SVG image on a
+    /// monospace grid, as a terminal would display it.
+    /// Colours are resolved through `options.palette`.
+    ///
+    /// Wide characters take two columns. Lines longer than
+    /// `options.columns` are cut off. Blinking is not animated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{fg, bg, style, ColouredString, SvgOptions};
+    /// let s = fg::red("error") + ": " + style::underline("oops") + "\n" + bg::blue("  ");
+    /// let svg = s.to_svg(&SvgOptions::default());
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(">error</text>"));
+    /// assert!(svg.contains("fill=\"#cd0000\""));
+    /// assert!(svg.contains("fill=\"#0000ee\""));
+    ///
+    /// let svg = style::invert("x").to_svg(&SvgOptions {
+    ///     frame : false,
+    ///     ..SvgOptions::default()
+    /// });
+    /// assert!(svg.contains("fill=\"#e5e5e5\""));
+    ///
+    /// let svg = ColouredString::from("日本 cut").to_svg(&SvgOptions {
+    ///     columns : Some(6),
+    ///     padding : 0.0,
+    ///     frame   : false,
+    ///     ..SvgOptions::default()
+    /// });
+    /// assert!(svg.contains("textLength=\"50.4\" lengthAdjust=\"spacingAndGlyphs\" fill=\"#e5e5e5\">日本 c</text>"));
+    /// ```
+    pub fn to_svg(&self, options : &SvgOptions) -> String {
+        let lines    = cells(self, options.columns.unwrap_or(usize::MAX));
+        let columns  = options.columns.unwrap_or_else(|| lines.iter().map(|line| line.iter().map(|(ch, _)| char_width(*ch)).sum()).max().unwrap_or(0));
+        let cell_w   = options.font_size * options.cell_width;
+        let cell_h   = options.font_size * options.line_height;
+        let bar_h    = if (options.frame) {cell_h * 2.0} else {0.0};
+        let width    = cell_w * columns as f64 + options.padding * 2.0;
+        let height   = cell_h * lines.len() as f64 + options.padding * 2.0 + bar_h;
+        let palette  = &options.palette;
+
+        let mut svg = String::new();
+        let _ = writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">", w = number(width), h = number(height));
+        let _ = writeln!(svg, "<rect width=\"100%\" height=\"100%\" rx=\"{}\" fill=\"{}\"/>", if (options.frame) {"6"} else {"0"}, palette.background);
+
+        if (options.frame) {
+            for (i, colour) in FRAME_BUTTONS.iter().enumerate() {
+                let _ = writeln!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"6\" fill=\"{}\"/>", number(options.padding + 6.0 + i as f64 * 20.0), number(bar_h / 2.0), colour);
+            }
+            if let Some(title) = &options.title {
+                let _ = writeln!(svg,
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" font-family=\"{}\" font-size=\"{}\" fill=\"{}\" opacity=\"0.7\">{}</text>",
+                    number(width / 2.0), number(bar_h / 2.0), escape(&options.font_family), number(options.font_size), palette.foreground, escape(title)
+                );
+            }
+        }
+
+        let _ = writeln!(svg, "<g font-family=\"{}\" font-size=\"{}\" xml:space=\"preserve\">", escape(&options.font_family), number(options.font_size));
+        for (row, line) in lines.iter().enumerate() {
+            let top      = options.padding + bar_h + cell_h * row as f64;
+            let baseline = top + (cell_h + options.font_size * 0.7) / 2.0;
+            let mut column = 0;
+            for run in line.chunk_by(|a, b| a.1 == b.1) {
+                let style  = run[0].1;
+                let x      = options.padding + cell_w * column as f64;
+                let span   = run.iter().map(|(ch, _)| char_width(*ch)).sum::<usize>();
+                let w      = cell_w * span as f64;
+                column    += span;
+
+                let (mut fg, mut bg) = (palette.foreground_of(&style), palette.background_of(&style));
+                if (style.is_invert()) {
+                    (fg, bg) = (bg, fg);
+                }
+                if (bg != palette.background) {
+                    let _ = writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>", number(x), number(top), number(w), number(cell_h), bg);
+                }
+                if (style.is_conceal()) {
+                    continue;
+                }
+
+                let text    = run.iter().map(|(ch, _)| *ch).collect::<String>();
+                let opacity = if (style.is_faint()) {" opacity=\"0.5\""} else {""};
+                if (! text.trim().is_empty()) {
+                    let _ = writeln!(svg,
+                        "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" fill=\"{}\"{}{}{}>{}</text>",
+                        number(x), number(baseline), number(w), fg,
+                        if (style.is_bold()) {" font-weight=\"bold\""} else {""},
+                        if (style.is_italic()) {" font-style=\"italic\""} else {""},
+                        opacity, escape(&text)
+                    );
+                }
+
+                let thickness   = number((options.font_size / 14.0).max(1.0));
+                let decorations = [
+                    (style.is_underline(),     baseline + options.font_size * 0.15),
+                    (style.is_overline(),      top + 1.0),
+                    (style.is_strikethrough(), baseline - options.font_size * 0.3)
+                ];
+                for (_, y) in decorations.iter().filter(|(enabled, _)| *enabled) {
+                    let _ = writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"{}/>", number(x), number(*y), number(w), thickness, fg, opacity);
+                }
+            }
+        }
+        svg.push_str("</g>\n</svg>\n");
+        return svg;
+    }
+
+}
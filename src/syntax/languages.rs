@@ -0,0 +1,225 @@
+//! The built-in languages.
+//!
+//! # Internal
+
+use crate::syntax::{
+    is_identifier,
+    identifier_length,
+    Language,
+    TokenPattern
+};
+
+
+/// Returns the length of the quoted string at `offset`
+/// if it is followed by a colon, as in a JSON key.
+fn json_key(text : &str, offset : usize) -> Option<usize> {
+    let length = TokenPattern::delimited("\"", "\"", Some('\\')).find(text, offset)?;
+    return text[offset + length..].trim_start().starts_with(':').then_some(length);
+}
+
+/// Returns the length of the bare or quoted key at `offset`
+/// if it starts a line and is followed by `=`, as in TOML.
+fn toml_key(text : &str, offset : usize) -> Option<usize> {
+    if (! text[..offset].rsplit('\n').next().unwrap_or_default().trim().is_empty()) {
+        return None;
+    }
+    let length = text[offset..].find(|ch : char| ! (is_identifier(ch) || matches!(ch, '-' | '.' | '"' | '\''))).unwrap_or(text.len() - offset);
+    return (length > 0 && text[offset + length..].trim_start_matches([' ', '\t']).starts_with('=')).then_some(length);
+}
+
+/// Returns the length of the table header at `offset`
+/// if it starts a line, such as `[package]`.
+fn toml_table(text : &str, offset : usize) -> Option<usize> {
+    if (! text[offset..].starts_with('[') || ! text[..offset].rsplit('\n').next().unwrap_or_default().trim().is_empty()) {
+        return None;
+    }
+    let line = &text[offset..text[offset..].find('\n').map_or(text.len(), |end| offset + end)];
+    return line.rfind(']').map(|end| end + 1);
+}
+
+/// Returns the length of the shell comment at `offset`.
+/// Comments must start a word.
+fn shell_comment(text : &str, offset : usize) -> Option<usize> {
+    if (! text[offset..].starts_with('#') || ! (offset == 0 || text[..offset].ends_with(char::is_whitespace) || text[..offset].ends_with(';'))) {
+        return None;
+    }
+    return Some(text[offset..].find('\n').unwrap_or(text.len() - offset));
+}
+
+/// Returns the length of the shell variable at `offset`,
+/// such as `$HOME`, `${HOME}`, `$1` or `$?`.
+fn shell_variable(text : &str, offset : usize) -> Option<usize> {
+    let rest = text[offset..].strip_prefix('$')?;
+    if (rest.starts_with('{')) {
+        return Some(rest.find('}').map_or(rest.len(), |end| end + 1) + 1);
+    }
+    return match (rest.chars().next()?) {
+        ch if ch.is_ascii_digit() || "?!#$@*-".contains(ch) => Some(2),
+        _ => {
+            let length = identifier_length(rest);
+            (length > 0).then_some(length + 1)
+        }
+    };
+}
+
+/// Returns the length of the Rust attribute at `offset`,
+/// such as `#[cfg(any(a, b[0]))]`, matching nested brackets
+/// and skipping strings.
+fn rust_attribute(text : &str, offset : usize) -> Option<usize> {
+    let rest  = &text[offset..];
+    let open  = ["#[", "#!["].into_iter().find(|open| rest.starts_with(open))?.len();
+    let mut depth = 1;
+    let mut chars = rest[open..].char_indices();
+    while let Some((idx, ch)) = chars.next() {
+        match (ch) {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if (depth == 0) {
+                    return Some(open + idx + 1);
+                }
+            },
+            '"' => while let Some((_, ch)) = chars.next() {
+                if (ch == '\\') {
+                    chars.next();
+                } else if (ch == '"') {
+                    break;
+                }
+            },
+            _   => { }
+        }
+    }
+    return Some(rest.len());
+}
+
+/// Returns the length of the identifier at `offset` if it
+/// is followed by `(` or `!`, as in a Rust call or macro.
+fn rust_call(text : &str, offset : usize) -> Option<usize> {
+    if (text[..offset].ends_with(is_identifier) || text[offset..].starts_with(|ch : char| ch.is_ascii_digit())) {
+        return None;
+    }
+    let length = identifier_length(&text[offset..]);
+    let next   = text[offset + length..].chars().next();
+    return (length > 0 && (next == Some('(') || (next == Some('!') && ! text[offset + length..].starts_with("!=")))).then_some(length + usize::from(next == Some('!')));
+}
+
+/// Returns the length of the character literal at `offset`,
+/// such as `'a'` or `'\n'`, which is not a lifetime.
+fn rust_char(text : &str, offset : usize) -> Option<usize> {
+    let rest  = text[offset..].strip_prefix('\'')?;
+    let inner = if let Some(escaped) = rest.strip_prefix('\\') {
+        escaped.find('\'').map(|end| end + 1)?
+    } else {
+        rest.chars().next()?.len_utf8()
+    };
+    return rest[inner..].starts_with('\'').then_some(inner + 2);
+}
+
+/// Returns the length of the identifier at `offset` if it
+/// is followed by a single `:`, as in a `Debug` field name.
+fn debug_field(text : &str, offset : usize) -> Option<usize> {
+    if (text[..offset].ends_with(is_identifier) || text[offset..].starts_with(|ch : char| ch.is_ascii_digit())) {
+        return None;
+    }
+    let length = identifier_length(&text[offset..]);
+    let rest   = &text[offset + length..];
+    return (length > 0 && rest.starts_with(':') && ! rest.starts_with("::")).then_some(length);
+}
+
+/// Returns the length of the identifier at `offset` if it
+/// is followed by ` {`, as in a `Debug` struct name.
+fn debug_struct(text : &str, offset : usize) -> Option<usize> {
+    if (text[..offset].ends_with(is_identifier) || text[offset..].starts_with(|ch : char| ch.is_ascii_digit())) {
+        return None;
+    }
+    let length = identifier_length(&text[offset..]);
+    return (length > 0 && text[offset + length..].starts_with(" {")).then_some(length);
+}
+
+
+/// JSON.
+pub(crate) fn json() -> Language {
+    return Language::new("json")
+        .rule("property",    TokenPattern::Custom(Box::new(json_key)))
+        .rule("string",      TokenPattern::delimited("\"", "\"", Some('\\')))
+        .rule("number",      TokenPattern::Number)
+        .rule("constant",    TokenPattern::keywords(&["true", "false", "null"]))
+        .rule("punctuation", TokenPattern::literals(&["{", "}", "[", "]", ",", ":"]));
+}
+
+/// TOML.
+pub(crate) fn toml() -> Language {
+    return Language::new("toml")
+        .rule("comment",     TokenPattern::ToLineEnd(String::from("#")))
+        .rule("type",        TokenPattern::Custom(Box::new(toml_table)))
+        .rule("property",    TokenPattern::Custom(Box::new(toml_key)))
+        .rule("string",      TokenPattern::delimited("\"\"\"", "\"\"\"", Some('\\')))
+        .rule("string",      TokenPattern::delimited("'''", "'''", None))
+        .rule("string",      TokenPattern::delimited("\"", "\"", Some('\\')))
+        .rule("string",      TokenPattern::delimited("'", "'", None))
+        .rule("number",      TokenPattern::Number)
+        .rule("constant",    TokenPattern::keywords(&["true", "false", "inf", "nan"]))
+        .rule("punctuation", TokenPattern::literals(&["{", "}", "[", "]", ",", "="]));
+}
+
+/// POSIX shell and bash.
+pub(crate) fn shell() -> Language {
+    return Language::new("shell")
+        .rule("comment",  TokenPattern::Custom(Box::new(shell_comment)))
+        .rule("variable", TokenPattern::Custom(Box::new(shell_variable)))
+        .rule("string",   TokenPattern::delimited("\"", "\"", Some('\\')))
+        .rule("string",   TokenPattern::delimited("'", "'", None))
+        .rule("keyword",  TokenPattern::keywords(&[
+            "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done",
+            "case", "esac", "in", "function", "select", "return", "local", "export", "readonly"
+        ]))
+        .rule("function", TokenPattern::keywords(&[
+            "echo", "printf", "cd", "exit", "read", "set", "unset", "source", "test", "shift", "eval", "exec", "trap"
+        ]))
+        .rule("number",   TokenPattern::Number)
+        .rule("operator", TokenPattern::literals(&["&&", "||", "|", ";;", ";", ">>", ">", "<", "2>", "&>", "&"]));
+}
+
+/// Rust.
+pub(crate) fn rust() -> Language {
+    return Language::new("rust")
+        .rule("comment",   TokenPattern::ToLineEnd(String::from("//")))
+        .rule("comment",   TokenPattern::delimited("/*", "*/", None))
+        .rule("attribute", TokenPattern::Custom(Box::new(rust_attribute)))
+        .rule("string",    TokenPattern::delimited("\"", "\"", Some('\\')))
+        .rule("string",    TokenPattern::delimited("r#\"", "\"#", None))
+        .rule("string",    TokenPattern::Custom(Box::new(rust_char)))
+        .rule("type",      TokenPattern::Custom(Box::new(|text : &str, offset : usize| {
+            let rest = text[offset..].strip_prefix('\'')?;
+            let length = identifier_length(rest);
+            return (length > 0).then_some(length + 1);
+        })))
+        .rule("keyword",   TokenPattern::keywords(&[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+            "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+            "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+            "where", "while"
+        ]))
+        .rule("constant",  TokenPattern::keywords(&["true", "false", "None", "Some", "Ok", "Err"]))
+        .rule("type",      TokenPattern::keywords(&[
+            "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize",
+            "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64"
+        ]))
+        .rule("function",  TokenPattern::Custom(Box::new(rust_call)))
+        .rule("constant",  TokenPattern::Identifier(Box::new(|word| word.len() > 1 && word.chars().all(|ch| ch.is_uppercase() || ch.is_ascii_digit() || ch == '_'))))
+        .rule("type",      TokenPattern::Identifier(Box::new(|word| word.starts_with(char::is_uppercase))))
+        .rule("number",    TokenPattern::Number);
+}
+
+/// The output of `Debug` formatting.
+pub(crate) fn debug() -> Language {
+    return Language::new("debug")
+        .rule("string",      TokenPattern::delimited("\"", "\"", Some('\\')))
+        .rule("string",      TokenPattern::Custom(Box::new(rust_char)))
+        .rule("property",    TokenPattern::Custom(Box::new(debug_field)))
+        .rule("type",        TokenPattern::Custom(Box::new(debug_struct)))
+        .rule("constant",    TokenPattern::keywords(&["true", "false", "inf", "NaN"]))
+        .rule("constant",    TokenPattern::Identifier(Box::new(|word| word.starts_with(char::is_uppercase))))
+        .rule("number",      TokenPattern::Number)
+        .rule("punctuation", TokenPattern::literals(&["{", "}", "[", "]", "(", ")", ",", ":"]));
+}
@@ -0,0 +1,325 @@
+use crate::{
+    builder::ColouredStringBuilder,
+    strings::ColouredString
+};
+
+pub(crate) mod languages;
+
+
+/// A function which returns the length of the token starting
+/// at the given byte offset of the text, if there is one.
+pub type TokenFunction = Box<dyn Fn(&str, usize) -> Option<usize>>;
+
+
+/// Returns `true` if a character can be part of an identifier.
+fn is_identifier(ch : char) -> bool {
+    return ch.is_alphanumeric() || ch == '_';
+}
+
+/// Returns the length of the identifier at the start of the text.
+fn identifier_length(text : &str) -> usize {
+    return text.find(|ch : char| ! is_identifier(ch)).unwrap_or(text.len());
+}
+
+
+/// Something which finds a token at a position of some text.
+pub enum TokenPattern {
+    /// Matches an identifier which is one of the given words.
+    /// Identifiers are runs of letters, digits and `_` which
+    /// do not start with a digit.
+    Keywords(Vec<String>),
+    /// Matches an identifier which satisfies a predicate.
+    Identifier(Box<dyn Fn(&str) -> bool>),
+    /// Matches the longest of the given strings.
+    Literals(Vec<String>),
+    /// Matches from an opening string to a closing string.
+    /// The escape character skips the character after it.
+    /// Unterminated tokens continue to the end of the text.
+    Delimited {
+        open   : String,
+        close  : String,
+        escape : Option<char>
+    },
+    /// Matches from a string to the end of the line.
+    ToLineEnd(String),
+    /// Matches a number, such as `12`, `0xff`, `1_000` or `1.5e-3`.
+    Number,
+    /// Matches the length returned by a function.
+    Custom(TokenFunction)
+}
+
+/// Initialisation
+impl TokenPattern {
+
+    /// Create a `TokenPattern::Keywords` from a list of words.
+    pub fn keywords(words : &[&str]) -> TokenPattern {
+        return TokenPattern::Keywords(words.iter().map(|word| word.to_string()).collect());
+    }
+
+    /// Create a `TokenPattern::Literals` from a list of strings.
+    pub fn literals(literals : &[&str]) -> TokenPattern {
+        return TokenPattern::Literals(literals.iter().map(|literal| literal.to_string()).collect());
+    }
+
+    /// Create a `TokenPattern::Delimited`.
+    pub fn delimited(open : &str, close : &str, escape : Option<char>) -> TokenPattern {
+        return TokenPattern::Delimited {
+            open   : open.to_string(),
+            close  : close.to_string(),
+            escape
+        };
+    }
+
+}
+
+/// Matching
+impl TokenPattern {
+
+    /// Returns the length of the token at `offset`, if any.
+    ///
+    /// # Internal
+    fn find(&self, text : &str, offset : usize) -> Option<usize> {
+        let rest = &text[offset..];
+        let word = || {
+            let boundary = ! text[..offset].ends_with(is_identifier) && ! rest.starts_with(|ch : char| ch.is_ascii_digit());
+            let length   = identifier_length(rest);
+            return (boundary && length > 0).then_some(length);
+        };
+        return match (self) {
+
+            TokenPattern::Keywords(words) => word().filter(|length| words.iter().any(|word| word == &rest[..*length])),
+
+            TokenPattern::Identifier(predicate) => word().filter(|length| predicate(&rest[..*length])),
+
+            TokenPattern::Literals(literals) => literals.iter().filter(|literal| rest.starts_with(literal.as_str())).map(|literal| literal.len()).max(),
+
+            TokenPattern::Delimited { open, close, escape } => {
+                if (open.is_empty() || ! rest.starts_with(open.as_str())) {
+                    return None;
+                }
+                let mut chars = rest[open.len()..].char_indices();
+                while let Some((idx, ch)) = chars.next() {
+                    if (Some(ch) == *escape) {
+                        chars.next();
+                    } else if (rest[open.len() + idx..].starts_with(close.as_str())) {
+                        return Some(open.len() + idx + close.len());
+                    }
+                }
+                Some(rest.len())
+            },
+
+            TokenPattern::ToLineEnd(start) => (! start.is_empty() && rest.starts_with(start.as_str())).then(|| rest.find('\n').unwrap_or(rest.len())),
+
+            TokenPattern::Number => {
+                if (! rest.starts_with(|ch : char| ch.is_ascii_digit()) || text[..offset].ends_with(is_identifier)) {
+                    return None;
+                }
+                let mut length = 0;
+                let mut chars  = rest.char_indices().peekable();
+                while let Some((idx, ch)) = chars.next() {
+                    let exponent = matches!(ch, 'e' | 'E') && ! rest.starts_with("0x");
+                    if (is_identifier(ch)) {
+                        length = idx + ch.len_utf8();
+                        if (exponent && matches!(chars.peek(), Some((_, '+' | '-')))) {
+                            chars.next();
+                            length += 1;
+                        }
+                    } else if (ch == '.' && rest[idx + 1..].starts_with(|ch : char| ch.is_ascii_digit())) {
+                        length = idx + 1;
+                    } else {
+                        break;
+                    }
+                }
+                Some(length)
+            },
+
+            TokenPattern::Custom(function) => function(text, offset)
+
+        };
+    }
+
+}
+
+
+/// A rule of a [Language], which gives every token
+/// matched by a pattern a kind.
+pub struct TokenRule {
+    /// The kind of the tokens, such as `keyword`. Tokens
+    /// are styled by the semantic name `syntax.<kind>`.
+    pub kind    : String,
+    /// The pattern which finds the tokens.
+    pub pattern : TokenPattern
+}
+
+
+/// The token rules of a language, used to highlight source text.
+///
+/// At each position of the text, the rules are tried in
+/// the order they were added, and the first match becomes
+/// a token. Text which no rule matches is left unstyled.
+///
+/// Tokens are given the semantic name `syntax.<kind>`, so
+/// their style comes from the active [Theme](crate::Theme).
+/// The built-in theme styles the kinds `keyword`, `string`,
+/// `number`, `comment`, `function`, `type`, `constant`,
+/// `property`, `variable`, `attribute`, `operator` and
+/// `punctuation`.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{Formatting, Language, TokenPattern};
+/// let language = Language::new("ini")
+///     .rule("comment",  TokenPattern::ToLineEnd(String::from(";")))
+///     .rule("type",     TokenPattern::delimited("[", "]", None))
+///     .rule("property", TokenPattern::Identifier(Box::new(|_| true)))
+///     .rule("number",   TokenPattern::Number);
+/// let s = language.highlight("[server]\nport = 8080 ; default");
+/// assert_eq!(s.unformat(), "[server]\nport = 8080 ; default");
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgYellow));
+/// assert_eq!(s.style_at(9).fg(), Some(Formatting::FgBlue));
+/// assert_eq!(s.style_at(16).fg(), Some(Formatting::FgCyan));
+/// assert!(s.style_at(21).is_italic());
+///
+/// let rust = Language::builtin("rs").unwrap();
+/// assert_eq!(rust.highlight("fn main() {}").style_at(0).fg(), Some(Formatting::FgMagenta));
+/// let s = rust.highlight("#[cfg(any(a, b[0]))] fn");
+/// assert_eq!(s.style_at(18).fg(), Some(Formatting::FgYellow));
+/// assert_eq!(s.style_at(21).fg(), Some(Formatting::FgMagenta));
+///
+/// let json = Language::builtin("json").unwrap();
+/// assert!(json.highlight("[1]").style_at(0).is_faint());
+/// ```
+pub struct Language {
+    name  : String,
+    rules : Vec<TokenRule>
+}
+
+/// Initialisation
+impl Language {
+
+    /// Create a new `Language` with no rules.
+    pub fn new<S : Into<String>>(name : S) -> Language {
+        return Language {
+            name  : name.into(),
+            rules : Vec::new()
+        };
+    }
+
+    /// Returns this `Language` with a rule added after the others.
+    pub fn rule<S : Into<String>>(mut self, kind : S, pattern : TokenPattern) -> Language {
+        self.rules.push(TokenRule {
+            kind : kind.into(),
+            pattern
+        });
+        return self;
+    }
+
+    /// Returns the names of the built-in languages.
+    pub fn builtin_names() -> &'static [&'static str] {
+        return &["json", "toml", "shell", "rust"];
+    }
+
+    /// Returns a built-in language by name or file extension,
+    /// such as `rust` or `rs`.
+    pub fn builtin(name : &str) -> Option<Language> {
+        return match (name.to_lowercase().as_str()) {
+            "json"                          => Some(languages::json()),
+            "toml"                          => Some(languages::toml()),
+            "shell" | "sh" | "bash" | "zsh" => Some(languages::shell()),
+            "rust" | "rs"                   => Some(languages::rust()),
+            _                               => None
+        };
+    }
+
+}
+
+/// Getters
+impl Language {
+
+    /// Returns the name of this `Language`.
+    pub fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    /// Returns the rules of this `Language`, in order.
+    pub fn rules(&self) -> &[TokenRule] {
+        return &self.rules;
+    }
+
+}
+
+/// Highlighting
+impl Language {
+
+    /// Split some source text into tokens, as
+    /// `(kind, text)`. Unmatched text has no kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::Language;
+    /// let json = Language::builtin("json").unwrap();
+    /// assert_eq!(json.tokens("{\"a\": 1}"), vec![
+    ///     (Some("punctuation"), "{"),
+    ///     (Some("property"),    "\"a\""),
+    ///     (Some("punctuation"), ":"),
+    ///     (None,                " "),
+    ///     (Some("number"),      "1"),
+    ///     (Some("punctuation"), "}")
+    /// ]);
+    /// ```
+    pub fn tokens<'l>(&'l self, source : &'l str) -> Vec<(Option<&'l str>, &'l str)> {
+        let mut result = Vec::<(Option<&str>, &str)>::new();
+        let mut plain  = 0;
+        let mut offset = 0;
+        while (offset < source.len()) {
+            let token = self.rules.iter().find_map(|rule| rule.pattern.find(source, offset)
+                .filter(|length| *length > 0 && source.is_char_boundary(offset + *length))
+                .map(|length| (rule.kind.as_str(), length.min(source.len() - offset)))
+            );
+            match (token) {
+                Some((kind, length)) => {
+                    if (plain < offset) {
+                        result.push((None, &source[plain..offset]));
+                    }
+                    result.push((Some(kind), &source[offset..offset + length]));
+                    offset += length;
+                    plain   = offset;
+                },
+                None => {
+                    let rest = &source[offset..];
+                    offset  += identifier_length(rest).max(rest.chars().next().map_or(1, |ch| ch.len_utf8()));
+                }
+            }
+        }
+        if (plain < source.len()) {
+            result.push((None, &source[plain..]));
+        }
+        return result;
+    }
+
+    /// Highlight some source text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::Language;
+    /// let json   = Language::builtin("json").unwrap();
+    /// let source = format!("[{}]", vec!["1"; 5000].join(", "));
+    /// let text   = json.highlight(&source);
+    /// assert_eq!(text.unformat(), source);
+    /// assert!(text.format().contains("1"));
+    /// ```
+    pub fn highlight(&self, source : &str) -> ColouredString {
+        let mut result = ColouredStringBuilder::new();
+        for (kind, text) in self.tokens(source) {
+            match (kind) {
+                Some(kind) => result.text(ColouredString::from_semantic(text, format!("syntax.{}", kind))),
+                None       => result.text(text)
+            };
+        }
+        return result.build();
+    }
+
+}
@@ -0,0 +1,597 @@
+use std::io::{
+    self,
+    Write
+};
+
+use crate::{
+    builder::ColouredStringBuilder,
+    consts::Formatting,
+    strings::ColouredString,
+    styles::Style,
+    width::char_width
+};
+
+
+/// The character of the cell covered by the right
+/// half of a wide character.
+pub(crate) const CONTINUATION : char = '\0';
+
+
+/// The state of the escape sequence parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    /// Printing text.
+    Ground,
+    /// After an `ESC`.
+    Escape,
+    /// Inside a control sequence, holding its parameters.
+    Csi(String),
+    /// Inside an operating system command.
+    Osc,
+    /// After an `ESC` inside an operating system command.
+    OscEscape
+}
+
+
+/// A character on a [Terminal] and its resolved `Style`.
+/// The cell covered by the right half of a wide
+/// character holds `'\0'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// The character.
+    pub ch    : char,
+    /// The style of the character.
+    pub style : Style
+}
+
+/// A blank cell.
+impl Default for Cell {
+    fn default() -> Cell {
+        return Cell {
+            ch    : ' ',
+            style : Style::new()
+        };
+    }
+}
+
+
+/// An in-memory terminal emulator, which turns the output
+/// of a program into a grid of styled characters.
+/// 
+/// SGR formatting, carriage returns, line feeds, tabs,
+/// backspaces, cursor movement, erasing, inserting and
+/// deleting, scrolling, and saving and restoring the
+/// cursor are supported. Other sequences are ignored.
+/// A line feed also returns the cursor to the first
+/// column, as a terminal does with `onlcr` set, so the
+/// output of [ColouredString::format] can be fed as is.
+/// 
+/// Wide characters take two cells, and characters which
+/// take no columns are dropped. Erased and inserted cells
+/// take the current background colour.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{bg, fg, style, Formatting, Terminal};
+/// let mut terminal = Terminal::new(20, 3);
+/// terminal.feed(&format!("{}\n", fg::red("error")));
+/// terminal.feed(&format!("{}", style::bold("x")));
+/// terminal.feed("\x1b[1;3HR");
+/// 
+/// let cell = terminal.cell(1, 0);
+/// assert_eq!(cell.ch, 'x');
+/// assert!(cell.style.is_bold());
+/// assert_eq!(terminal.cell(0, 2).ch, 'R');
+/// assert_eq!(terminal.cell(0, 2).style.fg(), None);
+/// assert_eq!(terminal.cell(0, 3).style.fg(), Some(Formatting::FgRed));
+/// assert_eq!(terminal.text(), "erRor\nx");
+/// assert_eq!(terminal.cursor(), (0, 3));
+/// 
+/// terminal.feed(&format!("\r{}\x1b[K日本", bg::blue("!")));
+/// assert_eq!(terminal.cell(0, 2).ch, '\0');
+/// assert_eq!(terminal.line(0).unformat(), "!日本");
+/// assert_eq!(terminal.cursor(), (0, 5));
+/// terminal.feed("\x1b[44m\x1b[K");
+/// assert_eq!(terminal.cell(0, 19).style.bg(), Some(Formatting::BgBlue));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Terminal {
+    width   : usize,
+    height  : usize,
+    cells   : Vec<Vec<Cell>>,
+    row     : usize,
+    column  : usize,
+    /// Whether the next character wraps to the next line.
+    wrap    : bool,
+    style   : Style,
+    saved   : (usize, usize, Style),
+    state   : State,
+    /// The start of a partial UTF-8 character written by `io::Write`.
+    pending : Vec<u8>
+}
+
+/// Initialisation
+impl Terminal {
+
+    /// Create a new blank `Terminal` with the given
+    /// number of columns and rows.
+    pub fn new(width : usize, height : usize) -> Terminal {
+        let width  = width.max(1);
+        let height = height.max(1);
+        return Terminal {
+            width,
+            height,
+            cells   : vec![vec![Cell::default(); width]; height],
+            row     : 0,
+            column  : 0,
+            wrap    : false,
+            style   : Style::new(),
+            saved   : (0, 0, Style::new()),
+            state   : State::Ground,
+            pending : Vec::new()
+        };
+    }
+
+    /// Change the size of this `Terminal`. Rows are
+    /// removed from the top if the cursor would be
+    /// outside of the new size.
+    pub fn resize(&mut self, width : usize, height : usize) {
+        let width  = width.max(1);
+        let height = height.max(1);
+        for line in &mut self.cells {
+            line.resize(width, Cell::default());
+        }
+        if (height < self.cells.len() && self.row >= height) {
+            self.cells.drain(..=self.row - height);
+        }
+        self.cells.resize(height, vec![Cell::default(); width]);
+        self.width  = width;
+        self.height = height;
+        self.row    = self.row.min(height - 1);
+        self.column = self.column.min(width - 1);
+        self.wrap   = false;
+    }
+
+}
+
+/// Getters
+impl Terminal {
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /// Returns the cursor position as `(row, column)`,
+    /// starting at `(0, 0)`.
+    pub fn cursor(&self) -> (usize, usize) {
+        return (self.row, self.column);
+    }
+
+    /// Returns the cell at a position.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the position is outside of the terminal.
+    pub fn cell(&self, row : usize, column : usize) -> Cell {
+        assert!(row < self.height && column < self.width, "Cell ({}, {}) is outside of the terminal.", row, column);
+        return self.cells[row][column];
+    }
+
+    /// Returns the cells of a row.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the row is outside of the terminal.
+    pub fn row(&self, row : usize) -> &[Cell] {
+        return &self.cells[row];
+    }
+
+    /// Returns a row as a `ColouredString`,
+    /// without trailing blank cells.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the row is outside of the terminal.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{fg, Formatting, Terminal};
+    /// let mut terminal = Terminal::new(10, 2);
+    /// terminal.feed(&format!("a{}  ", fg::blue("bc")));
+    /// let line = terminal.line(0);
+    /// assert_eq!(line.unformat(), "abc");
+    /// assert!(line.style_at(0).is_plain());
+    /// assert_eq!(line.style_at(1).fg(), Some(Formatting::FgBlue));
+    /// ```
+    pub fn line(&self, row : usize) -> ColouredString {
+        let cells = &self.cells[row];
+        let end   = cells.iter().rposition(|cell| *cell != Cell::default()).map_or(0, |end| end + 1);
+        let mut result = ColouredStringBuilder::new();
+        for run in cells[..end].chunk_by(|a, b| a.style == b.style) {
+            let text = run.iter().map(|cell| cell.ch).filter(|ch| *ch != CONTINUATION).collect::<String>();
+            if (run[0].style.is_plain()) {
+                result.text(text);
+            } else {
+                result.text(ColouredString::from_formatting(text, run[0].style.formatting()));
+            }
+        }
+        return result.build();
+    }
+
+    /// Returns every row as a `ColouredString`.
+    /// See [Terminal::line].
+    pub fn lines(&self) -> Vec<ColouredString> {
+        return (0..self.height).map(|row| self.line(row)).collect();
+    }
+
+    /// Returns the text of every row joined with newlines,
+    /// without trailing blank cells and rows.
+    pub fn text(&self) -> String {
+        return self.contents().unformat();
+    }
+
+    /// Returns every row joined with newlines,
+    /// without trailing blank cells and rows.
+    pub fn contents(&self) -> ColouredString {
+        let lines = self.lines();
+        let end   = lines.iter().rposition(|line| ! line.is_empty()).map_or(0, |end| end + 1);
+        let mut result = ColouredStringBuilder::new();
+        for (i, line) in lines.into_iter().take(end).enumerate() {
+            if (i > 0) {
+                result.text("\n");
+            }
+            result.text(line);
+        }
+        return result.build();
+    }
+
+}
+
+/// Output
+impl Terminal {
+
+    /// Process some output.
+    pub fn feed(&mut self, text : &str) {
+        for ch in text.chars() {
+            self.feed_char(ch);
+        }
+    }
+
+    /// See [Terminal::feed].
+    fn feed_char(&mut self, ch : char) {
+        match (std::mem::replace(&mut self.state, State::Ground)) {
+            State::Ground => match (ch) {
+                '\x1b' => self.state = State::Escape,
+                '\n'   => {
+                    self.column = 0;
+                    self.line_feed();
+                },
+                '\r'   => {
+                    self.column = 0;
+                    self.wrap   = false;
+                },
+                '\t'   => {
+                    self.column = ((self.column / 8 + 1) * 8).min(self.width - 1);
+                    self.wrap   = false;
+                },
+                '\x08' => {
+                    self.column = self.column.saturating_sub(1);
+                    self.wrap   = false;
+                },
+                ch if ch.is_control() => { },
+                ch     => self.print(ch)
+            },
+            State::Escape => match (ch) {
+                '['  => self.state = State::Csi(String::new()),
+                ']'  => self.state = State::Osc,
+                '7'  => self.saved = (self.row, self.column, self.style),
+                '8'  => self.restore(),
+                'D'  => self.line_feed(),
+                'E'  => {
+                    self.column = 0;
+                    self.line_feed();
+                },
+                'M'  => self.reverse_line_feed(),
+                'c'  => {
+                    self.cells  = vec![self.blank_line(); self.height];
+                    self.row    = 0;
+                    self.column = 0;
+                    self.style  = Style::new();
+                    self.saved  = (0, 0, Style::new());
+                },
+                _    => { }
+            },
+            State::Csi(mut parameters) => {
+                if (('\x40'..='\x7e').contains(&ch)) {
+                    self.control(&parameters, ch);
+                } else {
+                    parameters.push(ch);
+                    self.state = State::Csi(parameters);
+                }
+            },
+            State::Osc => match (ch) {
+                '\x07' => { },
+                '\x1b' => self.state = State::OscEscape,
+                _      => self.state = State::Osc
+            },
+            State::OscEscape => if (ch != '\\') {
+                self.state = State::Osc;
+            }
+        }
+    }
+
+    /// Write a character at the cursor. A wide
+    /// character which does not fit wraps early.
+    fn print(&mut self, ch : char) {
+        let width = char_width(ch);
+        if (width == 0 || width > self.width) {
+            return;
+        }
+        if (self.wrap || self.column + width > self.width) {
+            self.column = 0;
+            self.wrap   = false;
+            self.line_feed();
+        }
+        let style = self.style.resolved();
+        self.put(self.column, Cell { ch, style });
+        if (width == 2) {
+            self.put(self.column + 1, Cell { ch : CONTINUATION, style });
+        }
+        if (self.column + width < self.width) {
+            self.column += width;
+        } else {
+            self.column = self.width - 1;
+            self.wrap   = true;
+        }
+    }
+
+    /// Set a cell of the cursor row, blanking the other
+    /// half of any wide character which it overwrites.
+    fn put(&mut self, column : usize, cell : Cell) {
+        let line = &mut self.cells[self.row];
+        if (line[column].ch == CONTINUATION && column > 0) {
+            line[column - 1] = Cell::default();
+        }
+        if (char_width(line[column].ch) == 2 && column + 1 < self.width) {
+            line[column + 1] = Cell::default();
+        }
+        line[column] = cell;
+    }
+
+    /// Returns an erased cell, which takes the
+    /// current background colour.
+    fn blank(&self) -> Cell {
+        return Cell {
+            ch    : ' ',
+            style : self.style.bg().map_or(Style::new(), Style::from).resolved()
+        };
+    }
+
+    /// Move the cursor down, scrolling at the bottom.
+    fn line_feed(&mut self) {
+        self.wrap = false;
+        if (self.row + 1 < self.height) {
+            self.row += 1;
+        } else {
+            self.scroll_up(1);
+        }
+    }
+
+    /// Move the cursor up, scrolling at the top.
+    fn reverse_line_feed(&mut self) {
+        self.wrap = false;
+        if (self.row > 0) {
+            self.row -= 1;
+        } else {
+            self.scroll_down(1);
+        }
+    }
+
+    /// Returns a row of erased cells.
+    fn blank_line(&self) -> Vec<Cell> {
+        return vec![self.blank(); self.width];
+    }
+
+    /// Move every row up, adding erased rows at the bottom.
+    fn scroll_up(&mut self, count : usize) {
+        for _ in 0..count.min(self.height) {
+            self.cells.remove(0);
+            self.cells.push(self.blank_line());
+        }
+    }
+
+    /// Move every row down, adding erased rows at the top.
+    fn scroll_down(&mut self, count : usize) {
+        for _ in 0..count.min(self.height) {
+            self.cells.pop();
+            self.cells.insert(0, self.blank_line());
+        }
+    }
+
+    /// Restore the saved cursor and style.
+    fn restore(&mut self) {
+        let (row, column, style) = self.saved;
+        self.row    = row.min(self.height - 1);
+        self.column = column.min(self.width - 1);
+        self.style  = style;
+        self.wrap   = false;
+    }
+
+    /// Clear the cells of a row in a range of columns,
+    /// and the other half of any wide character cut in two.
+    fn erase(&mut self, row : usize, start : usize, end : usize) {
+        let (start, end) = (start.min(self.width), end.min(self.width));
+        let blank        = self.blank();
+        let line         = &mut self.cells[row];
+        if (start < end && start > 0 && line[start].ch == CONTINUATION) {
+            line[start - 1] = blank;
+        }
+        if (start < end && end < self.width && line[end].ch == CONTINUATION) {
+            line[end] = blank;
+        }
+        for cell in &mut line[start..end] {
+            *cell = blank;
+        }
+    }
+
+    /// Handle a control sequence.
+    fn control(&mut self, parameters : &str, command : char) {
+        if (parameters.starts_with(['?', '>', '<', '='])) {
+            return;
+        }
+        let numbers = parameters.split(';').map(|n| n.parse::<usize>().unwrap_or(0)).collect::<Vec<usize>>();
+        let n       = numbers.first().copied().unwrap_or(0);
+        let count   = n.max(1);
+        if (command != 'm') {
+            self.wrap = false;
+        }
+        match (command) {
+            'm' => match (Formatting::parse_sgr(parameters)) {
+                Some(formatting) if formatting.is_empty() => self.style = Style::new(),
+                Some(formatting) => for formatting in formatting {
+                    self.style.apply(formatting);
+                },
+                None => { }
+            },
+            'A' => self.row    = self.row.saturating_sub(count),
+            'B' => self.row    = (self.row + count).min(self.height - 1),
+            'C' => self.column = (self.column + count).min(self.width - 1),
+            'D' => self.column = self.column.saturating_sub(count),
+            'E' => {
+                self.row    = (self.row + count).min(self.height - 1);
+                self.column = 0;
+            },
+            'F' => {
+                self.row    = self.row.saturating_sub(count);
+                self.column = 0;
+            },
+            'G' | '`' => self.column = (count - 1).min(self.width - 1),
+            'd' => self.row = (count - 1).min(self.height - 1),
+            'H' | 'f' => {
+                self.row    = (count - 1).min(self.height - 1);
+                self.column = (numbers.get(1).copied().unwrap_or(0).max(1) - 1).min(self.width - 1);
+            },
+            'J' => {
+                let (row, column) = (self.row, self.column);
+                match (n) {
+                    0 => {
+                        self.erase(row, column, self.width);
+                        for row in row + 1..self.height {
+                            self.erase(row, 0, self.width);
+                        }
+                    },
+                    1 => {
+                        for row in 0..row {
+                            self.erase(row, 0, self.width);
+                        }
+                        self.erase(row, 0, column + 1);
+                    },
+                    _ => for row in 0..self.height {
+                        self.erase(row, 0, self.width);
+                    }
+                }
+            },
+            'K' => {
+                let (row, column) = (self.row, self.column);
+                match (n) {
+                    0 => self.erase(row, column, self.width),
+                    1 => self.erase(row, 0, column + 1),
+                    _ => self.erase(row, 0, self.width)
+                }
+            },
+            'X' => {
+                let (row, column) = (self.row, self.column);
+                self.erase(row, column, column + count);
+            },
+            'P' => {
+                let blank = self.blank();
+                let line  = &mut self.cells[self.row];
+                let end   = (self.column + count).min(self.width);
+                line.drain(self.column..end);
+                line.resize(self.width, blank);
+            },
+            '@' => {
+                let blank = self.blank();
+                let line  = &mut self.cells[self.row];
+                for _ in 0..count.min(self.width - self.column) {
+                    line.insert(self.column, blank);
+                }
+                line.truncate(self.width);
+            },
+            'L' => for _ in 0..count.min(self.height - self.row) {
+                self.cells.pop();
+                self.cells.insert(self.row, self.blank_line());
+            },
+            'M' => for _ in 0..count.min(self.height - self.row) {
+                self.cells.remove(self.row);
+                self.cells.push(self.blank_line());
+            },
+            'S' => self.scroll_up(count),
+            'T' => self.scroll_down(count),
+            's' => self.saved = (self.row, self.column, self.style),
+            'u' => self.restore(),
+            _   => { }
+        }
+    }
+
+}
+
+/// Feeds the bytes as output. Invalid UTF-8 is replaced.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use std::io::Write;
+/// use vibrance::Terminal;
+/// let mut terminal = Terminal::new(10, 1);
+/// terminal.write_all(&[b'a', 0xff, 0xe6]).unwrap();
+/// terminal.write_all(&[0x97, 0xa5]).unwrap();
+/// assert_eq!(terminal.text(), "a\u{FFFD}日");
+/// ```
+impl Write for Terminal {
+    fn write(&mut self, buf : &[u8]) -> io::Result<usize> {
+        let text = take_utf8(&mut self.pending, buf);
+        self.feed(&text);
+        return Ok(buf.len());
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        return Ok(());
+    }
+}
+
+
+/// Adds bytes to a buffer, and removes and returns all of it
+/// except the start of a character which is not complete yet.
+/// Invalid UTF-8 is replaced.
+///
+/// # Internal
+pub(crate) fn take_utf8(pending : &mut Vec<u8>, buf : &[u8]) -> String {
+    pending.extend_from_slice(buf);
+    let mut end = 0;
+    loop {
+        match (std::str::from_utf8(&pending[end..])) {
+            Ok(_)      => {
+                end = pending.len();
+                break;
+            },
+            Err(error) => match (error.error_len()) {
+                Some(length) => end += error.valid_up_to() + length,
+                None         => {
+                    end += error.valid_up_to();
+                    break;
+                }
+            }
+        }
+    }
+    let bytes = pending.drain(..end).collect::<Vec<u8>>();
+    return String::from_utf8_lossy(&bytes).into_owned();
+}
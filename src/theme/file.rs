@@ -0,0 +1,148 @@
+use std::{
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter
+    },
+    fs,
+    io,
+    path::Path,
+    str::FromStr
+};
+
+use crate::{
+    styles::Style,
+    theme::Theme
+};
+
+
+/// An error produced when a theme file can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeParseError {
+    /// The line of the error, starting at 1.
+    pub line    : usize,
+    /// The column of the error in characters, starting at 1.
+    pub column  : usize,
+    /// A description of the error.
+    pub message : String
+}
+
+impl Display for ThemeParseError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}:{}: {}", self.line, self.column, self.message);
+    }
+}
+
+impl Error for ThemeParseError { }
+
+
+/// Theme Files
+/// 
+/// A theme file contains one style per line, in the
+/// form `name = description`. Descriptions are lists
+/// of formatting names and colours, such as
+/// `bold red on #202020`. See the `FromStr`
+/// implementation of `Style`. Blank lines and lines
+/// starting with `#` are ignored.
+/// 
+/// ```text
+/// # Diagnostics
+/// error      = bold red on #202020
+/// error.code = underline
+/// warning    = bold 214
+/// ```
+impl Theme {
+
+    /// Parse a theme file.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The contents of the theme file.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Theme};
+    /// let theme = Theme::parse("error = bold red on #202020\nwarning = yellow").unwrap();
+    /// let error = theme.get("error").unwrap();
+    /// assert!(error.is_bold());
+    /// assert_eq!(error.fg(), Some(Formatting::FgRed));
+    /// assert_eq!(error.bg(), Some(Formatting::Bg24Bit(32, 32, 32)));
+    /// 
+    /// let error = Theme::parse("error = bold\nwarning = yelow").unwrap_err();
+    /// assert_eq!((error.line, error.column), (2, 11));
+    /// ```
+    pub fn parse(text : &str) -> Result<Theme, ThemeParseError> {
+        let mut theme = Theme::new();
+        for (idx, line) in text.lines().enumerate() {
+            let error = |offset : usize, message : String| ThemeParseError {
+                line    : idx + 1,
+                column  : line[..offset].chars().count() + 1,
+                message
+            };
+            let content = line.trim_start();
+            if (content.is_empty() || content.starts_with('#')) {
+                continue;
+            }
+            let start  = line.len() - content.len();
+            let equals = line.find('=').ok_or_else(|| error(line.len(), String::from("Expected `=` after the style name.")))?;
+            let name   = line[..equals].trim();
+            if (name.is_empty()) {
+                return Err(error(start, String::from("Expected a style name.")));
+            }
+            if let Some(ch) = name.chars().find(|&ch| ! (ch.is_alphanumeric() || ch == '_' || ch == '-' || ch == '.')) {
+                return Err(error(start + name.find(ch).unwrap_or(0), format!("Invalid character `{}` in style name.", ch)));
+            }
+            let description = &line[equals + 1..];
+            let style = description.parse::<Style>().map_err(|parse_error| error(equals + 1 + parse_error.offset, parse_error.message))?;
+            theme.set(name, style);
+        }
+        return Ok(theme);
+    }
+
+    /// Load a theme file from a path.
+    /// 
+    /// # Errors
+    /// 
+    /// * The file can not be read.
+    /// * The file can not be parsed. The error has a kind of
+    ///   [io::ErrorKind::InvalidData] and wraps a [ThemeParseError].
+    pub fn load<P : AsRef<Path>>(path : P) -> io::Result<Theme> {
+        return Theme::parse(&fs::read_to_string(path)?).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error));
+    }
+
+    /// Create the contents of a theme file which
+    /// [Theme::parse] parses back into this `Theme`.
+    /// Styles are sorted by name.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Style, Theme};
+    /// let theme = Theme::new()
+    ///     .with("error",      Style::from(vec![Formatting::Bold, Formatting::FgRed, Formatting::Bg24Bit(32, 32, 32)]))
+    ///     .with("error.code", Style::from(Formatting::Underline));
+    /// assert_eq!(theme.serialize(), "error = bold red on #202020\nerror.code = underline\n");
+    /// assert_eq!(Theme::parse(&theme.serialize()).unwrap(), theme);
+    /// ```
+    pub fn serialize(&self) -> String {
+        let mut names = self.styles.keys().collect::<Vec<&String>>();
+        names.sort();
+        return names.into_iter().map(|name| format!("{} = {}\n", name, self.styles[name])).collect();
+    }
+
+    /// Write a theme file to a path.
+    /// See [Theme::serialize].
+    pub fn save<P : AsRef<Path>>(&self, path : P) -> io::Result<()> {
+        return fs::write(path, self.serialize());
+    }
+
+}
+
+impl FromStr for Theme {
+    type Err = ThemeParseError;
+    fn from_str(text : &str) -> Result<Theme, ThemeParseError> {
+        return Theme::parse(text);
+    }
+}
@@ -0,0 +1,164 @@
+use std::env;
+
+use crate::{
+    consts::Formatting,
+    styles::Style,
+    theme::Theme
+};
+
+
+/// The `GREP_COLORS` used by GNU `grep` when the variable is not set.
+const DEFAULT_GREP_COLORS : &str = "ms=01;31:mc=01;31:sl=:cx=:fn=35:ln=32:bn=32:se=36";
+
+/// The `GCC_COLORS` used by GCC when the variable is not set.
+const DEFAULT_GCC_COLORS : &str = "error=01;31:warning=01;35:note=01;36:range1=32:range2=34:locus=01:quote=01:path=01;36:fixit-insert=32:fixit-delete=31:diff-filename=01:diff-hunk=32:diff-delete=31:diff-insert=32:type-diff=01;32";
+
+
+/// Imports
+impl Theme {
+
+    /// Parse a list of `key=SGR` entries separated by `:`,
+    /// on top of the given defaults.
+    /// Entries without a value or with malformed codes are ignored.
+    /// 
+    /// # Internal
+    fn from_sgr_list(text : &str, defaults : &str) -> Theme {
+        let mut theme = Theme::new();
+        for entry in defaults.split(':').chain(text.split(':')) {
+            if let Some((key, codes)) = entry.split_once('=') {
+                if let Some(formatting) = Formatting::parse_sgr(codes) {
+                    theme.set(key, Style::from(formatting));
+                }
+            }
+        }
+        return theme;
+    }
+
+    /// Parse a `GREP_COLORS` value, such as `ms=01;31:fn=35`,
+    /// on top of the defaults of GNU `grep`.
+    /// Each key, such as `ms` or `fn`, becomes a style name.
+    /// `mt` sets both `ms` and `mc`. Boolean capabilities
+    /// such as `rv` and `ne` are ignored.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Theme};
+    /// let theme = Theme::from_grep_colors("mt=01;32:fn=34:ne");
+    /// assert_eq!(theme.get("ms").unwrap().fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(theme.get("mc").unwrap().fg(), Some(Formatting::FgGreen));
+    /// assert_eq!(theme.get("fn").unwrap().fg(), Some(Formatting::FgBlue));
+    /// assert_eq!(theme.get("ln").unwrap().fg(), Some(Formatting::FgGreen));
+    /// ```
+    pub fn from_grep_colors(text : &str) -> Theme {
+        let mut theme = Theme::from_sgr_list(text, DEFAULT_GREP_COLORS);
+        if let Some(style) = theme.remove("mt") {
+            theme.set("ms", style);
+            theme.set("mc", style);
+        }
+        return theme;
+    }
+
+    /// Parse the `GREP_COLORS` environment variable.
+    /// See [Theme::from_grep_colors].
+    pub fn from_grep_colors_env() -> Theme {
+        return Theme::from_grep_colors(&env::var("GREP_COLORS").unwrap_or_default());
+    }
+
+    /// Parse a `GCC_COLORS` value, such as `error=01;31:note=01;36`,
+    /// on top of the defaults of GCC.
+    /// Each key, such as `error` or `locus`, becomes a style name.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Theme};
+    /// let theme = Theme::from_gcc_colors("error=01;35:quote=");
+    /// assert_eq!(theme.get("error").unwrap().fg(), Some(Formatting::FgMagenta));
+    /// assert!(theme.get("quote").unwrap().is_plain());
+    /// assert!(theme.get("locus").unwrap().is_bold());
+    /// ```
+    pub fn from_gcc_colors(text : &str) -> Theme {
+        return Theme::from_sgr_list(text, DEFAULT_GCC_COLORS);
+    }
+
+    /// Parse the `GCC_COLORS` environment variable.
+    /// See [Theme::from_gcc_colors].
+    pub fn from_gcc_colors_env() -> Theme {
+        return Theme::from_gcc_colors(&env::var("GCC_COLORS").unwrap_or_default());
+    }
+
+    /// Parse the `color.*` settings of a git config file, or
+    /// the output of `git config --get-regexp '^color\.'`.
+    /// 
+    /// Each setting, such as `color.diff.meta`, becomes a
+    /// style name without the `color.` prefix, such as `diff.meta`.
+    /// Values use git's colour syntax, such as `bold red blue ul`,
+    /// which is parsed by the `FromStr` implementation of `Style`.
+    /// Settings which are not colours, such as `color.ui = auto`,
+    /// are ignored.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Theme};
+    /// let theme = Theme::from_git_config("
+    ///     [color]
+    ///         ui = auto
+    ///     [color \"diff\"]
+    ///         meta = bold yellow
+    ///         old = red reverse
+    ///     [core]
+    ///         pager = less
+    /// ");
+    /// assert_eq!(theme.get("diff.meta").unwrap().fg(), Some(Formatting::FgYellow));
+    /// assert!(theme.get("diff.old").unwrap().is_invert());
+    /// assert_eq!(theme.get("ui"), None);
+    /// 
+    /// let theme = Theme::from_git_config("color.branch.current brightgreen normal ul\n");
+    /// assert_eq!(theme.get("branch.current").unwrap().fg(), Some(Formatting::FgBrightGreen));
+    /// ```
+    pub fn from_git_config(text : &str) -> Theme {
+        let mut theme   = Theme::new();
+        let mut section = None;
+        for line in text.lines() {
+            let line = line.trim();
+            if (line.is_empty() || line.starts_with('#') || line.starts_with(';')) {
+                continue;
+            }
+            let (name, value) = if let Some(header) = line.strip_prefix('[') {
+                let header = header.split(']').next().unwrap_or_default().trim();
+                section = match (header.split_once(char::is_whitespace)) {
+                    Some((name, subsection)) => Some((name.to_lowercase(), Some(subsection.trim().trim_matches('"').to_string()))),
+                    None                     => Some((header.to_lowercase(), None))
+                };
+                continue;
+            } else if let Some(rest) = line.strip_prefix("color.") {
+                match (rest.split_once(char::is_whitespace)) {
+                    Some((name, value)) => (name.to_string(), value),
+                    None                => continue
+                }
+            } else if let (Some((section, subsection)), Some((key, value))) = (&section, line.split_once('=')) {
+                if (section != "color") {
+                    continue;
+                }
+                let key = key.trim().to_lowercase();
+                match (subsection) {
+                    Some(subsection) => (format!("{}.{}", subsection, key), value),
+                    None             => (key, value)
+                }
+            } else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            if (matches!(value, "auto" | "always" | "never" | "true" | "false")) {
+                continue;
+            }
+            if let Ok(style) = value.parse::<Style>() {
+                theme.set(name, style);
+            }
+        }
+        return theme;
+    }
+
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        OnceLock,
+        RwLock
+    }
+};
+
+use crate::{
+    traits::Colourisable,
+    consts::Formatting,
+    strings::ColouredString,
+    styles::Style
+};
+
+mod file;
+mod imports;
+pub use file::ThemeParseError;
+
+
+/// The theme used to resolve semantic names when formatting.
+/// `None` means the default theme is used.
+static ACTIVE_THEME : RwLock<Option<Theme>> = RwLock::new(None);
+
+/// The default theme, created on first use.
+static DEFAULT_THEME : OnceLock<Theme> = OnceLock::new();
+
+
+/// A mapping from semantic names to styles.
+/// 
+/// Names are split into segments by `.`. Each name
+/// inherits the style of its parent, so `error.code`
+/// is `error` with the style of `error.code` applied
+/// on top.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use vibrance::{Formatting, Style, Theme};
+/// let theme = Theme::new()
+///     .with("error",      Style::from(vec![Formatting::Bold, Formatting::FgRed]))
+///     .with("error.code", Style::from(Formatting::Underline));
+/// let s = theme.style("error.code", "E0001");
+/// assert!(s.style_at(0).is_bold());
+/// assert!(s.style_at(0).is_underline());
+/// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgRed));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    styles : HashMap<String, Style>
+}
+
+/// Initialisation
+impl Theme {
+    /// Create a new `Theme` with no styles.
+    pub fn new() -> Theme {
+        return Theme {
+            styles : HashMap::new()
+        };
+    }
+
+    /// Returns this `Theme` with a style set.
+    /// See [Theme::set].
+    pub fn with<S : Into<String>>(mut self, name : S, style : Style) -> Theme {
+        self.set(name, style);
+        return self;
+    }
+}
+
+/// The built-in theme.
+impl Default for Theme {
+    fn default() -> Theme {
+        return Theme::new()
+            .with("error",    Style::from(vec![Formatting::Bold, Formatting::FgRed]))
+            .with("warning",  Style::from(vec![Formatting::Bold, Formatting::FgYellow]))
+            .with("info",     Style::from(vec![Formatting::FgBlue]))
+            .with("note",     Style::from(vec![Formatting::Bold, Formatting::FgCyan]))
+            .with("help",     Style::from(vec![Formatting::Bold, Formatting::FgGreen]))
+            .with("success",  Style::from(vec![Formatting::FgGreen]))
+            .with("emphasis", Style::from(vec![Formatting::Bold]))
+            .with("muted",    Style::from(vec![Formatting::Faint]))
+            .with("diagnostic.gutter",    Style::from(vec![Formatting::Bold, Formatting::FgBlue]))
+            .with("diagnostic.secondary", Style::from(vec![Formatting::Bold, Formatting::FgBlue]))
+            .with("syntax.keyword",       Style::from(vec![Formatting::FgMagenta]))
+            .with("syntax.string",        Style::from(vec![Formatting::FgGreen]))
+            .with("syntax.number",        Style::from(vec![Formatting::FgCyan]))
+            .with("syntax.comment",       Style::from(vec![Formatting::Italic, Formatting::FgBrightBlack]))
+            .with("syntax.function",      Style::from(vec![Formatting::FgBlue]))
+            .with("syntax.type",          Style::from(vec![Formatting::FgYellow]))
+            .with("syntax.constant",      Style::from(vec![Formatting::FgCyan]))
+            .with("syntax.property",      Style::from(vec![Formatting::FgBlue]))
+            .with("syntax.variable",      Style::from(vec![Formatting::FgRed]))
+            .with("syntax.attribute",     Style::from(vec![Formatting::FgYellow]))
+            .with("syntax.operator",      Style::from(vec![Formatting::FgMagenta]))
+            .with("syntax.punctuation",   Style::from(vec![Formatting::Faint]));
+    }
+}
+
+/// Mutation and Getters
+impl Theme {
+
+    /// Set the style of a semantic name.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The semantic name, such as `error` or `error.code`.
+    /// * `style` - The `Style` to use for the name.
+    pub fn set<S : Into<String>>(&mut self, name : S, style : Style) {
+        self.styles.insert(name.into(), style);
+    }
+
+    /// Remove the style of a semantic name.
+    /// Returns the removed style, if any.
+    pub fn remove(&mut self, name : &str) -> Option<Style> {
+        return self.styles.remove(name);
+    }
+
+    /// Returns the style of a semantic name, including
+    /// the styles it inherits from its parents.
+    /// Returns `None` if neither the name nor any of
+    /// its parents have a style.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{Formatting, Theme};
+    /// let theme = Theme::default();
+    /// assert_eq!(theme.get("error.code"), theme.get("error"));
+    /// assert_eq!(theme.get("error").unwrap().fg(), Some(Formatting::FgRed));
+    /// assert_eq!(theme.get("unknown"), None);
+    /// ```
+    pub fn get(&self, name : &str) -> Option<Style> {
+        let parent = name.rfind('.').and_then(|idx| self.get(&name[..idx]));
+        return match (parent, self.styles.get(name)) {
+            (Some(parent), Some(style)) => Some(parent.merge(style)),
+            (parent, style)             => parent.or(style.copied())
+        };
+    }
+
+    /// Create a new `ColouredString` with the style
+    /// of a semantic name applied.
+    /// Unknown names apply no formatting.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The semantic name, such as `error` or `error.code`.
+    /// * `text` - Any `Colourisable` to format.
+    pub fn style<S : Colourisable>(&self, name : &str, text : S) -> ColouredString {
+        return self.get(name).unwrap_or_default().paint(text);
+    }
+
+}
+
+/// Active Theme
+impl Theme {
+
+    /// Returns a copy of the theme currently used to
+    /// resolve semantic names.
+    pub fn active() -> Theme {
+        return match (&*ACTIVE_THEME.read().unwrap_or_else(|error| error.into_inner())) {
+            Some(theme) => theme.clone(),
+            None        => Theme::default()
+        };
+    }
+
+    /// Set the theme used to resolve semantic names
+    /// for the whole process.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use vibrance::{semantic, Formatting, Style, Theme};
+    /// let s = semantic::warning("careful");
+    /// Theme::set_active(Theme::new().with("warning", Style::from(Formatting::FgMagenta)));
+    /// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgMagenta));
+    /// Theme::reset_active();
+    /// assert_eq!(s.style_at(0).fg(), Some(Formatting::FgYellow));
+    /// ```
+    pub fn set_active(theme : Theme) {
+        *ACTIVE_THEME.write().unwrap_or_else(|error| error.into_inner()) = Some(theme);
+    }
+
+    /// Go back to using the built-in theme to
+    /// resolve semantic names.
+    pub fn reset_active() {
+        *ACTIVE_THEME.write().unwrap_or_else(|error| error.into_inner()) = None;
+    }
+
+    /// Returns the style of a semantic name in the active theme.
+    /// 
+    /// # Internal
+    pub(crate) fn resolve_active(name : &str) -> Style {
+        return match (&*ACTIVE_THEME.read().unwrap_or_else(|error| error.into_inner())) {
+            Some(theme) => theme.get(name),
+            None        => DEFAULT_THEME.get_or_init(Theme::default).get(name)
+        }.unwrap_or_default();
+    }
+
+}
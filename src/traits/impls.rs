@@ -51,9 +51,9 @@ impl Add<ColouredString> for String {
         return string;
     }
 }
-impl Into<String> for ColouredString {
-    fn into(self) -> String {
-        return self.unformat();
+impl From<ColouredString> for String {
+    fn from(string : ColouredString) -> Self {
+        return string.unformat();
     }
 }
 impl From<String> for ColouredString {
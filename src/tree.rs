@@ -0,0 +1,247 @@
+use crate::{
+    consts::Formatting,
+    strings::ColouredString,
+    styles::Style
+};
+
+
+/// The characters used to draw the guides of a [Tree].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TreeGuides {
+    /// `├── `, `└── ` and `│   `.
+    #[default]
+    Unicode,
+    /// `|-- `, `` `-- `` and `|   `.
+    Ascii
+}
+
+/// Getters
+impl TreeGuides {
+
+    /// Returns the guides before a child which has siblings
+    /// after it, before the last child, and below a child
+    /// which has siblings after it.
+    pub fn parts(&self) -> [&'static str; 3] {
+        return match (self) {
+            TreeGuides::Unicode => ["├── ", "└── ", "│   "],
+            TreeGuides::Ascii   => ["|-- ", "`-- ", "|   "]
+        };
+    }
+
+}
+
+
+/// Options for [Tree::render].
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{Formatting, Style, TreeGuides, TreeOptions};
+/// let options = TreeOptions {
+///     guides    : TreeGuides::Ascii,
+///     styles    : vec![Style::from(Formatting::FgBlue), Style::from(Formatting::FgCyan)],
+///     max_depth : Some(3),
+///     ..TreeOptions::default()
+/// };
+/// assert!(options.hidden.is_faint());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeOptions {
+    /// The characters of the guides.
+    pub guides    : TreeGuides,
+    /// The styles of the guides below each depth, starting
+    /// with the guides to the children of the root. The last
+    /// style is used for every deeper level. If empty, the
+    /// guides are not styled.
+    pub styles    : Vec<Style>,
+    /// The depth below which nodes are hidden, with the root
+    /// at depth `0`. If `None`, every level is shown.
+    pub max_depth : Option<usize>,
+    /// The note after a node whose children are hidden, such as `(+3)`.
+    pub hidden    : Style
+}
+
+/// Initialisation
+impl TreeOptions {
+
+    /// Create new `TreeOptions` with the default values.
+    pub fn new() -> TreeOptions {
+        return TreeOptions::default();
+    }
+
+}
+
+impl Default for TreeOptions {
+    fn default() -> TreeOptions {
+        return TreeOptions {
+            guides    : TreeGuides::Unicode,
+            styles    : Vec::new(),
+            max_depth : None,
+            hidden    : Style::from(Formatting::Faint)
+        };
+    }
+}
+
+/// Getters
+impl TreeOptions {
+
+    /// Returns the style of the guides below a depth.
+    ///
+    /// # Internal
+    fn style(&self, depth : usize) -> Style {
+        return self.styles.get(depth).or(self.styles.last()).copied().unwrap_or_default();
+    }
+
+}
+
+
+/// A node of a tree, with a label and children.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::{fg, Tree, TreeOptions};
+/// let tree = Tree::new(fg::blue("vibrance"))
+///     .with_child(Tree::new("src")
+///         .with_child(Tree::new("lib.rs"))
+///         .with_child(Tree::new("tree.rs")))
+///     .with_child(Tree::new("target").with_collapsed(true)
+///         .with_child(Tree::new("debug")))
+///     .with_child(Tree::new("Cargo.toml\n(manifest)"));
+/// assert_eq!(tree.render(&TreeOptions::default()).unformat(), [
+///     "vibrance\n",
+///     "├── src\n",
+///     "│   ├── lib.rs\n",
+///     "│   └── tree.rs\n",
+///     "├── target (+1)\n",
+///     "└── Cargo.toml\n",
+///     "    (manifest)\n"
+/// ].concat());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tree {
+    /// The label of the node, which may span several lines.
+    pub label     : ColouredString,
+    /// The children of the node.
+    pub children  : Vec<Tree>,
+    /// Whether the children of the node are hidden.
+    pub collapsed : bool
+}
+
+/// Initialisation
+impl Tree {
+
+    /// Create a new `Tree` with no children.
+    pub fn new<S : Into<ColouredString>>(label : S) -> Tree {
+        return Tree {
+            label     : label.into(),
+            children  : Vec::new(),
+            collapsed : false
+        };
+    }
+
+    /// Returns this `Tree` with a child added after the others.
+    pub fn with_child(mut self, child : Tree) -> Tree {
+        self.children.push(child);
+        return self;
+    }
+
+    /// Returns this `Tree` with children added after the others.
+    pub fn with_children<I : IntoIterator<Item = Tree>>(mut self, children : I) -> Tree {
+        self.children.extend(children);
+        return self;
+    }
+
+    /// Returns this `Tree` with its children hidden or shown.
+    pub fn with_collapsed(mut self, collapsed : bool) -> Tree {
+        self.collapsed = collapsed;
+        return self;
+    }
+
+}
+
+/// Getters
+impl Tree {
+
+    /// Returns the number of nodes below this one.
+    pub fn descendants(&self) -> usize {
+        return self.children.iter().map(|child| child.descendants() + 1).sum();
+    }
+
+}
+
+/// Rendering
+impl Tree {
+
+    /// Render this `Tree` with guides, one node per line.
+    ///
+    /// Nodes whose children are hidden, because they are
+    /// collapsed or at `max_depth`, are followed by the number
+    /// of hidden nodes, such as `(+3)`. The lines after the
+    /// first of a label line up with the first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{Formatting, Style, Tree, TreeGuides, TreeOptions};
+    /// let tree = Tree::new("a").with_children([
+    ///     Tree::new("b").with_child(Tree::new("c").with_child(Tree::new("d"))),
+    ///     Tree::new("e")
+    /// ]);
+    /// let options = TreeOptions {
+    ///     guides    : TreeGuides::Ascii,
+    ///     styles    : vec![Style::from(Formatting::FgBlue), Style::from(Formatting::FgCyan)],
+    ///     max_depth : Some(2),
+    ///     ..TreeOptions::default()
+    /// };
+    /// let s = tree.render(&options);
+    /// assert_eq!(s.unformat(), "a\n|-- b\n|   `-- c (+1)\n`-- e\n");
+    /// assert_eq!(s.style_at(2).fg(), Some(Formatting::FgBlue));
+    /// assert_eq!(s.style_at(8).fg(), Some(Formatting::FgBlue));
+    /// assert_eq!(s.style_at(12).fg(), Some(Formatting::FgCyan));
+    /// ```
+    pub fn render(&self, options : &TreeOptions) -> ColouredString {
+        let mut result = ColouredString::new();
+        self.render_into(&mut result, options, &ColouredString::new(), ColouredString::new(), ColouredString::new(), 0);
+        return result;
+    }
+
+    /// Render this node and its children to the end of a `ColouredString`.
+    ///
+    /// * `prefix` - The guides of the ancestors.
+    /// * `branch` - The guide before the first line of the label.
+    /// * `below`  - The guide before the other lines of the label.
+    ///
+    /// # Internal
+    fn render_into(&self, result : &mut ColouredString, options : &TreeOptions, prefix : &ColouredString, branch : ColouredString, below : ColouredString, depth : usize) {
+        let hidden = ! self.children.is_empty() && (self.collapsed || options.max_depth.is_some_and(|max_depth| depth >= max_depth));
+        let lines  = self.label.lines();
+        for (idx, line) in lines.iter().enumerate() {
+            result.push(prefix.clone() + if (idx == 0) {branch.clone()} else {below.clone()} + line.clone());
+            if (hidden && idx + 1 == lines.len()) {
+                result.push(" ");
+                result.push(options.hidden.paint(format!("(+{})", self.descendants())));
+            }
+            result.push("\n");
+        }
+        if (lines.is_empty()) {
+            result.push(prefix.clone() + branch + "\n");
+        }
+        if (hidden) {
+            return;
+        }
+
+        let prefix = prefix.clone() + below;
+        let style  = options.style(depth);
+        let [middle, last, vertical] = options.guides.parts();
+        for (idx, child) in self.children.iter().enumerate() {
+            let is_last = idx + 1 == self.children.len();
+            child.render_into(result, options, &prefix,
+                style.paint(if (is_last) {last} else {middle}),
+                style.paint(if (is_last) {"    "} else {vertical}),
+                depth + 1
+            );
+        }
+    }
+
+}
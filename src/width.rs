@@ -0,0 +1,150 @@
+use crate::strings::ColouredString;
+
+
+/// Ranges of characters which take no columns.
+const ZERO_WIDTH : [(u32, u32); 22] = [
+    (0x0300, 0x036f), (0x0483, 0x0489), (0x0591, 0x05bd), (0x05bf, 0x05c7),
+    (0x0610, 0x061a), (0x064b, 0x065f), (0x0670, 0x0670), (0x06d6, 0x06ed),
+    (0x0900, 0x0903), (0x093a, 0x094f), (0x0e31, 0x0e3a), (0x0e47, 0x0e4e),
+    (0x1ab0, 0x1aff), (0x1dc0, 0x1dff), (0x200b, 0x200f), (0x202a, 0x202e),
+    (0x2060, 0x2064), (0x20d0, 0x20ff), (0xfe00, 0xfe0f), (0xfe20, 0xfe2f),
+    (0xfeff, 0xfeff), (0xe0100, 0xe01ef)
+];
+
+/// Ranges of characters which take two columns.
+const DOUBLE_WIDTH : [(u32, u32); 22] = [
+    (0x1100, 0x115f), (0x231a, 0x231b), (0x2329, 0x232a), (0x23e9, 0x23ec),
+    (0x25fd, 0x25fe), (0x2614, 0x2615), (0x26aa, 0x26ab), (0x26bd, 0x26be),
+    (0x2705, 0x2705), (0x274c, 0x274c), (0x2e80, 0x303e), (0x3041, 0x33ff),
+    (0x3400, 0x4dbf), (0x4e00, 0x9fff), (0xa000, 0xa4cf), (0xac00, 0xd7a3),
+    (0xf900, 0xfaff), (0xfe30, 0xfe4f), (0xff00, 0xff60), (0xffe0, 0xffe6),
+    (0x1f300, 0x1faff), (0x20000, 0x3fffd)
+];
+
+
+/// Returns the number of terminal columns a character takes.
+///
+/// Control characters and combining marks take no columns,
+/// and wide characters, such as CJK ideographs and most
+/// emoji, take two.
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::char_width;
+/// assert_eq!(char_width('a'), 1);
+/// assert_eq!(char_width('界'), 2);
+/// assert_eq!(char_width('\u{301}'), 0);
+/// ```
+pub fn char_width(ch : char) -> usize {
+    let code     = ch as u32;
+    let contains = |ranges : &[(u32, u32)]| ranges.iter().any(|(start, end)| (*start..=*end).contains(&code));
+    return if (ch.is_control() || contains(&ZERO_WIDTH)) {0}
+        else if (contains(&DOUBLE_WIDTH)) {2}
+        else {1};
+}
+
+/// Returns the number of terminal columns some text takes.
+/// Tabs take no columns, as their width depends on where
+/// they are. See [ColouredString::expand_tabs].
+///
+/// # Examples
+///
+/// ```
+/// use vibrance::str_width;
+/// assert_eq!(str_width("hello"), 5);
+/// assert_eq!(str_width("日本語"), 6);
+/// assert_eq!(str_width("e\u{301}"), 1);
+/// ```
+pub fn str_width(text : &str) -> usize {
+    return text.chars().map(char_width).sum();
+}
+
+
+/// Width
+impl ColouredString {
+
+    /// Returns the number of terminal columns this
+    /// `ColouredString` takes, ignoring its formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::fg;
+    /// let s = fg::red("日本") + "!";
+    /// assert_eq!(s.len(), 7);
+    /// assert_eq!(s.width(), 5);
+    /// ```
+    pub fn width(&self) -> usize {
+        return str_width(&self.unformat());
+    }
+
+    /// Returns this `ColouredString` with each tab replaced by
+    /// spaces up to the next multiple of `tab_width` columns,
+    /// keeping its formatting and semantic names. Columns are
+    /// counted from the start of each line. A `tab_width` of
+    /// `0` removes tabs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::{fg, ColouredString, Formatting};
+    /// let s = fg::red("a\tb") + "\n日\tc";
+    /// let expanded = s.expand_tabs(4);
+    /// assert_eq!(expanded.unformat(), "a   b\n日  c");
+    /// assert_eq!(expanded.style_at(2).fg(), Some(Formatting::FgRed));
+    ///
+    /// let s = ColouredString::from_semantic("\tfailed", "error");
+    /// assert_eq!(s.expand_tabs(2).semantic(), Some("error"));
+    /// ```
+    pub fn expand_tabs(&self, tab_width : usize) -> ColouredString {
+        let mut result = self.clone();
+        let mut column = 0;
+        result.map_text(|text| {
+            let mut expanded = String::with_capacity(text.len());
+            for ch in text.chars() {
+                match (ch) {
+                    '\t' => {
+                        let spaces = if (tab_width == 0) {0} else {tab_width - column % tab_width};
+                        expanded.extend(std::iter::repeat_n(' ', spaces));
+                        column += spaces;
+                    },
+                    '\n' => {
+                        expanded.push(ch);
+                        column = 0;
+                    },
+                    _    => {
+                        expanded.push(ch);
+                        column += char_width(ch);
+                    }
+                }
+            }
+            return expanded;
+        });
+        return result;
+    }
+
+    /// Shortens this `ColouredString` to at most
+    /// `width` terminal columns, keeping its formatting.
+    /// Wide characters which would be cut in half are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vibrance::fg;
+    /// let mut s = fg::red("日本") + "語";
+    /// s.truncate_width(3);
+    /// assert_eq!(s.unformat(), "日");
+    /// ```
+    pub fn truncate_width(&mut self, width : usize) {
+        let mut columns = 0;
+        for (idx, ch) in self.unformat().char_indices() {
+            columns += char_width(ch);
+            if (columns > width) {
+                self.truncate(idx);
+                return;
+            }
+        }
+    }
+
+}